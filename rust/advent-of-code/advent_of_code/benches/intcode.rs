@@ -0,0 +1,76 @@
+use advent_of_code::computer::{Computer, ComputerActions};
+use advent_of_code::solution::Solution;
+use advent_of_code::year_2019::day_two::DayTwo;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+const DAY_TWO_INPUT: &str = include_str!("../inputs/2019/day_two/input.txt");
+
+fn day_two_program() -> Vec<i64> {
+    DAY_TWO_INPUT
+        .trim_end()
+        .split(',')
+        .map(|v| v.parse::<i64>().unwrap())
+        .collect()
+}
+
+fn day_two(c: &mut Criterion) {
+    let program = day_two_program();
+    let mut group = c.benchmark_group("day_two");
+
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("single_run", |b| {
+        let mut computer = Computer::new(12, 2, program.clone());
+        b.iter(|| {
+            computer.reset(12, 2);
+            computer.run()
+        })
+    });
+
+    group.bench_function("noun_verb_search", |b| {
+        b.iter(|| DayTwo::part_two(&program))
+    });
+    group.finish();
+}
+
+// Runs the noun/verb search by hand so the same search can be timed with and without the
+// instruction cache
+fn search(computer: &mut Computer) -> Option<(i64, i64)> {
+    for noun in 0..100 {
+        for verb in 0..100 {
+            computer.reset(noun, verb);
+            if computer.run_to_halt().is_ok() && computer.memory()[0] == 19_690_720 {
+                return Some((noun, verb));
+            }
+        }
+    }
+    None
+}
+
+fn instruction_cache(c: &mut Criterion) {
+    let program = day_two_program();
+    let mut group = c.benchmark_group("instruction_cache");
+
+    for cached in &[false, true] {
+        let name = if *cached { "cached" } else { "interpreter" };
+        let mut computer = Computer::new(12, 2, program.clone());
+        if *cached {
+            computer.enable_instruction_cache();
+        }
+
+        group.bench_with_input(BenchmarkId::new("single_run", name), cached, |b, _| {
+            b.iter(|| {
+                computer.reset(12, 2);
+                computer.run()
+            })
+        });
+        group.bench_with_input(
+            BenchmarkId::new("noun_verb_search", name),
+            cached,
+            |b, _| b.iter(|| search(&mut computer)),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, day_two, instruction_cache);
+criterion_main!(benches);
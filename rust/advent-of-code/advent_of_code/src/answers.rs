@@ -0,0 +1,180 @@
+// The answers each part's solution is known to give for its input, so solutions can be checked
+// against them. They're recorded as the solutions return them, which isn't always what the site
+// asks for. They're kept in answers.toml in the inputs directory, a table per year and day with a
+// key per part:
+//
+//     [2019.2]
+//     1 = 3760627
+//     2 = 19690720
+use crate::error::Error;
+use crate::input::read_input;
+use crate::registry::Puzzle;
+use crate::solution::{Answer, Part};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(untagged)]
+enum Recorded {
+    Number(i64),
+    Text(String),
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(transparent)]
+pub struct Answers {
+    years: BTreeMap<String, BTreeMap<String, BTreeMap<String, Recorded>>>,
+}
+
+/// How an answer compares with the recorded one.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "verdict", rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    Incorrect {
+        expected: String,
+    },
+    /// There's no recorded answer to check against.
+    Unknown,
+}
+
+impl Answers {
+    pub fn parse(answers: &str) -> Result<Self, Error> {
+        Ok(toml::from_str(answers)?)
+    }
+
+    /// Loads the answers file, treating a missing file as having no recorded answers.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        match fs::read_to_string(path) {
+            Ok(answers) => Self::parse(&answers),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(Error::Custom(format!(
+                "Couldn't read {}: {}",
+                path.display(),
+                e
+            ))),
+        }
+    }
+
+    pub fn expected(&self, year: u32, day: usize, part: Part) -> Option<String> {
+        let recorded = self
+            .years
+            .get(&year.to_string())?
+            .get(&day.to_string())?
+            .get(&part.to_string())?;
+        Some(recorded.to_string())
+    }
+
+    pub fn check(&self, year: u32, day: usize, part: Part, answer: &str) -> Verdict {
+        match self.expected(year, day, part) {
+            Some(expected) if expected == answer => Verdict::Correct,
+            Some(expected) => Verdict::Incorrect { expected },
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+pub fn default_path(inputs_dir: &Path) -> PathBuf {
+    inputs_dir.join(ANSWERS_FILE)
+}
+
+/// Solves a puzzle from its input in the inputs directory and checks each part it gives.
+pub fn verify(
+    puzzle: &Puzzle,
+    inputs_dir: &Path,
+    answers: &Answers,
+    parts: &[Part],
+) -> Result<Vec<(Answer, Verdict)>, Error> {
+    let input = read_input(puzzle.year, puzzle.day, None, inputs_dir)?;
+    let solved = (puzzle.solve)(&input, parts)?;
+    Ok(solved
+        .answers
+        .into_iter()
+        .map(|answer| {
+            let verdict = answers.check(puzzle.year, puzzle.day, answer.part, &answer.answer);
+            (answer, verdict)
+        })
+        .collect())
+}
+
+impl fmt::Display for Recorded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Recorded::Number(n) => write!(f, "{}", n),
+            Recorded::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::Incorrect { expected } => write!(f, "incorrect, expected {}", expected),
+            Verdict::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: &str = "[2019.1]\n1 = 3216744\n2 = \"4822249\"\n\n[2019.3]\n1 = 1084\n";
+
+    #[test]
+    fn recorded_answers_can_be_numbers_or_strings() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+        assert_eq!(
+            answers.expected(2019, 1, Part::One),
+            Some(String::from("3216744"))
+        );
+        assert_eq!(
+            answers.expected(2019, 1, Part::Two),
+            Some(String::from("4822249"))
+        );
+    }
+
+    #[test]
+    fn answers_are_checked_against_the_recorded_ones() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+        assert_eq!(
+            answers.check(2019, 1, Part::One, "3216744"),
+            Verdict::Correct
+        );
+        assert_eq!(
+            answers.check(2019, 3, Part::One, "1085"),
+            Verdict::Incorrect {
+                expected: String::from("1084")
+            }
+        );
+        assert_eq!(answers.check(2019, 3, Part::Two, "9240"), Verdict::Unknown);
+        assert_eq!(
+            answers.check(2018, 1, Part::One, "3216744"),
+            Verdict::Unknown
+        );
+    }
+
+    #[test]
+    fn a_missing_answers_file_has_no_answers() {
+        let answers = Answers::load(Path::new("no/such/answers.toml")).unwrap();
+        assert_eq!(answers, Answers::default());
+    }
+
+    #[test]
+    fn verdicts_serialize_with_their_expected_answer() {
+        let verdict = Verdict::Incorrect {
+            expected: String::from("1084"),
+        };
+        assert_eq!(
+            serde_json::to_string(&verdict).unwrap(),
+            r#"{"verdict":"incorrect","expected":"1084"}"#
+        );
+    }
+}
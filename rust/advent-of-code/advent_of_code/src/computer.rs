@@ -1,41 +1,167 @@
-use std::io;
+pub mod amplifier;
+pub mod ascii;
+pub mod assembler;
+pub mod cache;
+pub mod debugger;
+pub mod disassembler;
+pub mod instruction;
+pub mod io;
+pub mod network;
+pub mod search;
+pub mod snapshot;
+pub mod symbolic;
+pub mod trace;
 
-#[derive(Clone)]
-pub struct Computer {
-    noun: i32,
-    verb: i32,
-    instruction_pointer: usize,
-    params: Option<Vec<usize>>,
-    pub start_input: Vec<i32>,
-    pub computed_values: Vec<i32>,
-    current_values: Values,
-    pub outputs: Vec<i32>,
+use self::cache::InstructionCache;
+use self::instruction::{Instruction, Opcode, ParamMode};
+use self::io::{Input, Output};
+use self::snapshot::Snapshot;
+use self::trace::{TraceEntry, Tracer};
+use crate::error::{Error, IntcodeError, IntcodeErrorKind};
+use crate::parse;
+use std::collections::VecDeque;
+
+/// The most values memory can grow to hold. Writing past it is a fault rather than an attempt to
+/// allocate however much the address asks for.
+pub const MEMORY_LIMIT: usize = 1 << 20;
+
+/// What the computer did on its last step, or why it stopped running.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Status {
+    /// The instruction ran and the program can carry on.
+    Running,
+    /// The program reached opcode 99.
+    Halted,
+    /// The program is waiting on an input instruction with nothing to read. Push an input and
+    /// run it again to carry on from the same instruction.
+    NeedsInput,
+    /// The program output a value.
+    Output(i64),
+    /// The program can't carry on, e.g. it reached an unknown opcode.
+    Error(IntcodeError),
+}
+
+/// Parses a comma separated Intcode program, ignoring surrounding whitespace.
+pub fn parse_program(input: &str) -> Result<Vec<i64>, Error> {
+    Ok(parse::comma_list(parse::Input::new(input))?)
 }
 
 #[derive(Clone)]
-pub struct Values {
-    values: Option<Vec<i32>>,
-    address: usize,
+pub struct Computer {
+    noun: i64,
+    verb: i64,
+    instruction_pointer: usize,
+    relative_base: i64,
+    start_input: Vec<i64>,
+    computed_values: Vec<i64>,
+    inputs: VecDeque<i64>,
+    input_source: Option<Box<dyn Input>>,
+    output_sink: Option<Box<dyn Output>>,
+    pub outputs: Vec<i64>,
+    last_write: Option<(usize, i64)>,
+    tracer: Option<Box<dyn Tracer>>,
+    // The cache of the program as loaded, which `reset` goes back to, and the cache of memory
+    // as it is now. Both are None unless the cache has been enabled.
+    start_cache: Option<InstructionCache>,
+    cache: Option<InstructionCache>,
 }
 
 impl ComputerActions for Computer {
-    fn new(noun: i32, verb: i32, start_input: Vec<i32>) -> Computer {
+    fn new(noun: i64, verb: i64, start_input: Vec<i64>) -> Self {
         Computer {
             noun,
             verb,
-            params: None,
             instruction_pointer: 0,
+            relative_base: 0,
             computed_values: start_input.clone(),
             start_input,
-            current_values: Values {
-                values: None,
-                address: 0,
-            },
+            inputs: VecDeque::new(),
+            input_source: None,
+            output_sink: None,
             outputs: Vec::new(),
+            last_write: None,
+            tracer: None,
+            start_cache: None,
+            cache: None,
         }
     }
 
-    fn write(&mut self, value: i32, action: &str) {
+    fn instruction_pointer(&self) -> usize {
+        self.instruction_pointer
+    }
+
+    fn relative_base(&self) -> i64 {
+        self.relative_base
+    }
+
+    // The address and value written by the last instruction that ran, if it wrote anything
+    fn last_write(&self) -> Option<(usize, i64)> {
+        self.last_write
+    }
+
+    fn memory(&self) -> &[i64] {
+        &self.computed_values
+    }
+
+    // The program as loaded, which `reset` goes back to
+    fn start_input(&self) -> &[i64] {
+        &self.start_input
+    }
+
+    // Memory is left as it is, but the next `reset` loads the new program. The cache of the
+    // program as loaded is compiled again so it can't go stale.
+    fn set_start_input(&mut self, start_input: Vec<i64>) {
+        self.start_input = start_input;
+        if self.cache.is_some() {
+            self.enable_instruction_cache();
+        }
+    }
+
+    fn enable_instruction_cache(&mut self) {
+        let start_cache = InstructionCache::compile(&self.start_input);
+        let mut cache = start_cache.clone();
+        // Anything written before the cache was enabled is decoded from memory, as if it had
+        // been written since
+        for (address, value) in self.computed_values.iter().enumerate() {
+            if self.start_input.get(address) != Some(value) {
+                cache.invalidate(address);
+            }
+        }
+        self.start_cache = Some(start_cache);
+        self.cache = Some(cache);
+    }
+
+    fn set_tracer(&mut self, tracer: Box<dyn Tracer>) {
+        self.tracer = Some(tracer);
+    }
+
+    fn push_input(&mut self, value: i64) {
+        self.inputs.push_back(value);
+    }
+
+    fn set_input(&mut self, input: Box<dyn Input>) {
+        self.input_source = Some(input);
+    }
+
+    fn set_output(&mut self, output: Box<dyn Output>) {
+        self.output_sink = Some(output);
+    }
+
+    fn read_input(&mut self) -> Option<i64> {
+        match self.inputs.pop_front() {
+            Some(value) => Some(value),
+            None => self.input_source.as_mut().and_then(|source| source.read()),
+        }
+    }
+
+    fn emit_output(&mut self, value: i64) {
+        if let Some(sink) = self.output_sink.as_mut() {
+            sink.write(value);
+        }
+        self.outputs.push(value);
+    }
+
+    fn write(&mut self, value: i64, action: &str) {
         if action == "noun" {
             self.noun = value;
         } else if action == "verb" {
@@ -43,236 +169,489 @@ impl ComputerActions for Computer {
         };
     }
 
-    // To implement param mode, each value needs to be computed (grabbed the address or used the value)
-    fn run(&mut self) {
-        let opcode = self.format_instructions(self.computed_values[self.instruction_pointer]);
-        if opcode == 99 {
-            return;
-        } else {
-            match opcode {
-                1 => {
-                    self.compute_values(2);
-                    let computed_values = self.current_values.values.as_ref().unwrap();
-                    self.computed_values[self.current_values.address] =
-                        computed_values[0] + computed_values[1];
-                    self.instruction_pointer += 4;
-                }
-                2 => {
-                    self.compute_values(2);
-                    let computed_values = self.current_values.values.as_ref().unwrap();
-                    self.computed_values[self.current_values.address] =
-                        computed_values[0] * computed_values[1];
-                    self.instruction_pointer += 4;
-                }
-                // 3 - Take an input and store at address
-                3 => {
-                    print!("Please enter your input\n");
-                    self.compute_values(0);
-                    let mut input = String::new();
-                    io::stdin()
-                        .read_line(&mut input)
-                        .ok()
-                        .expect("Couldn't read line");
-
-                    self.computed_values[self.current_values.address] =
-                        input.trim().parse::<i32>().unwrap();
-                    self.instruction_pointer += 2;
-                }
-                // 4 - Print the value at address
-                4 => {
-                    if self.params.is_some() {
-                        let output_value = self.computed_values[self.instruction_pointer + 1];
-                        println!("{}", output_value);
-                        self.outputs.push(output_value)
-                    } else {
-                        self.compute_values(0);
-                        let output_value = self.computed_values[self.current_values.address];
-                        println!("{}", output_value);
-                        self.outputs.push(output_value);
-                    }
-                    self.instruction_pointer += 2;
-                }
-                // 5 - if the first parameter is non-zero, it sets the instruction pointer to the value from the second parameter.
-                // Otherwise, it does nothing.
-                5 => {
-                    self.compute_values(2);
-                    let computed_values = self.current_values.values.as_ref().unwrap();
-                    if computed_values[0] != 0 {
-                        self.instruction_pointer = computed_values[1] as usize;
-                    } else {
-                        self.instruction_pointer += 3;
-                    }
-                }
-                // 6 - if the first parameter is zero, it sets the instruction pointer to the value from the second parameter.
-                // Otherwise, it does nothing.
-                6 => {
-                    self.compute_values(2);
-                    let computed_values = self.current_values.values.as_ref().unwrap();
-                    if computed_values[0] == 0 {
-                        self.instruction_pointer = computed_values[1] as usize;
-                    } else {
-                        self.instruction_pointer += 3;
-                    }
-                }
-                // 7 - if the first parameter is less than the second parameter, it stores 1 in the position given by the third parameter.
-                // Otherwise, it stores 0.
-                7 => {
-                    self.compute_values(2);
-                    let computed_values = self.current_values.values.as_ref().unwrap();
-                    if computed_values[0] < computed_values[1] {
-                        self.computed_values[self.current_values.address] = 1;
-                    } else {
-                        self.computed_values[self.current_values.address] = 0;
-                    }
-                    self.instruction_pointer += 4;
+    fn step(&mut self) -> Status {
+        self.last_write = None;
+        let trace_entry = match self.tracer {
+            Some(_) => self.trace_entry(),
+            None => None,
+        };
+        let status = match self.execute() {
+            Ok(status) => status,
+            Err(kind) => {
+                return Status::Error(IntcodeError {
+                    kind,
+                    instruction_pointer: self.instruction_pointer,
+                    instruction: self.read_memory(self.instruction_pointer),
+                })
+            }
+        };
+        if let (Some(tracer), Some(mut entry)) = (self.tracer.as_mut(), trace_entry) {
+            if status != Status::NeedsInput {
+                entry.write = self.last_write;
+                tracer.record(&entry);
+            }
+        }
+        status
+    }
+
+    // Resolves the parameters the next instruction is about to read, before it runs
+    fn trace_entry(&self) -> Option<TraceEntry> {
+        let instruction = self.decode(self.instruction_pointer).ok()?;
+        let operands = (0..instruction.opcode.param_count())
+            .filter(|param| Some(*param) != instruction.opcode.written_param())
+            .map(|param| self.read_param(&instruction, param).ok())
+            .collect::<Option<Vec<i64>>>()?;
+        Some(TraceEntry {
+            instruction_pointer: self.instruction_pointer,
+            instruction,
+            operands,
+            write: None,
+        })
+    }
+
+    fn execute(&mut self) -> Result<Status, IntcodeErrorKind> {
+        let instruction_pointer = self.instruction_pointer;
+        let instruction = self.decode(instruction_pointer)?;
+        let next_instruction = instruction_pointer + instruction.size();
+
+        match instruction.opcode {
+            Opcode::Halt => return Ok(Status::Halted),
+            Opcode::Add => {
+                let value = checked(
+                    self.read_param(&instruction, 0)?
+                        .checked_add(self.read_param(&instruction, 1)?),
+                )?;
+                self.write_param(&instruction, 2, value)?;
+            }
+            Opcode::Multiply => {
+                let value = checked(
+                    self.read_param(&instruction, 0)?
+                        .checked_mul(self.read_param(&instruction, 1)?),
+                )?;
+                self.write_param(&instruction, 2, value)?;
+            }
+            // Take an input and store at address
+            // If there's no input available yet, stop on this instruction so the program
+            // can carry on from here once one has been pushed
+            Opcode::Input => match self.read_input() {
+                Some(input) => self.write_param(&instruction, 0, input)?,
+                None => return Ok(Status::NeedsInput),
+            },
+            Opcode::Output => {
+                let value = self.read_param(&instruction, 0)?;
+                self.emit_output(value);
+                self.instruction_pointer = next_instruction;
+                return Ok(Status::Output(value));
+            }
+            // If the first parameter is non-zero, set the instruction pointer to the value from the second parameter.
+            Opcode::JumpIfTrue => {
+                if self.read_param(&instruction, 0)? != 0 {
+                    self.instruction_pointer = to_address(self.read_param(&instruction, 1)?)?;
+                    return Ok(Status::Running);
                 }
-                // 8 - if the first parameter is equal to the second parameter, it stores 1 in the position given by the third parameter.
-                // Otherwise, it stores 0.
-                8 => {
-                    self.compute_values(2);
-                    let computed_values = self.current_values.values.as_ref().unwrap();
-                    if computed_values[0] == computed_values[1] {
-                        self.computed_values[self.current_values.address] = 1;
-                    } else {
-                        self.computed_values[self.current_values.address] = 0;
-                    }
-                    self.instruction_pointer += 4;
+            }
+            // If the first parameter is zero, set the instruction pointer to the value from the second parameter.
+            Opcode::JumpIfFalse => {
+                if self.read_param(&instruction, 0)? == 0 {
+                    self.instruction_pointer = to_address(self.read_param(&instruction, 1)?)?;
+                    return Ok(Status::Running);
                 }
-                _ => return,
             }
-            self.current_values.values = None;
-            self.params = None;
-            self.run();
+            // Store 1 in the third parameter if the first parameter is less than the second, otherwise store 0.
+            Opcode::LessThan => {
+                let value = self.read_param(&instruction, 0)? < self.read_param(&instruction, 1)?;
+                self.write_param(&instruction, 2, value as i64)?;
+            }
+            // Store 1 in the third parameter if the first parameter is equal to the second, otherwise store 0.
+            Opcode::Equals => {
+                let value =
+                    self.read_param(&instruction, 0)? == self.read_param(&instruction, 1)?;
+                self.write_param(&instruction, 2, value as i64)?;
+            }
+            // Move the relative base by the value of the only parameter
+            Opcode::AdjustRelativeBase => {
+                self.relative_base = checked(
+                    self.relative_base
+                        .checked_add(self.read_param(&instruction, 0)?),
+                )?;
+            }
+        }
+        self.instruction_pointer = next_instruction;
+        Ok(Status::Running)
+    }
+
+    fn run_until(&mut self) -> Status {
+        loop {
+            match self.step() {
+                Status::Running => continue,
+                status => return status,
+            }
         }
     }
 
-    fn reset(&mut self, noun: i32, verb: i32) {
-        self.computed_values = self.start_input.clone();
+    fn run(&mut self) -> Status {
+        loop {
+            match self.run_until() {
+                Status::Output(_) => continue,
+                status => return status,
+            }
+        }
+    }
+
+    // Runs the program to the end, treating running out of inputs as a fault
+    fn run_to_halt(&mut self) -> Result<(), IntcodeError> {
+        match self.run() {
+            Status::Error(error) => Err(error),
+            Status::NeedsInput => Err(IntcodeError {
+                kind: IntcodeErrorKind::MissingInput,
+                instruction_pointer: self.instruction_pointer,
+                instruction: self.read_memory(self.instruction_pointer),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            noun: self.noun,
+            verb: self.verb,
+            instruction_pointer: self.instruction_pointer,
+            relative_base: self.relative_base,
+            start_input: self.start_input.clone(),
+            memory: self.computed_values.clone(),
+            inputs: self.inputs.iter().copied().collect(),
+            outputs: self.outputs.clone(),
+        }
+    }
+
+    // Keeps the input source, output sink and tracer, which aren't part of a snapshot
+    fn restore(&mut self, snapshot: &Snapshot) {
+        self.noun = snapshot.noun;
+        self.verb = snapshot.verb;
+        self.instruction_pointer = snapshot.instruction_pointer;
+        self.relative_base = snapshot.relative_base;
+        self.start_input = snapshot.start_input.clone();
+        self.computed_values = snapshot.memory.clone();
+        self.inputs = snapshot.inputs.iter().copied().collect();
+        self.outputs = snapshot.outputs.clone();
+        self.last_write = None;
+        if self.cache.is_some() {
+            self.enable_instruction_cache();
+        }
+    }
+
+    // Copies into the memory and cache already there rather than allocating new ones, as the
+    // same computer is often reset thousands of times
+    fn reset(&mut self, noun: i64, verb: i64) {
+        self.computed_values.clone_from(&self.start_input);
+        if let (Some(cache), Some(start_cache)) = (self.cache.as_mut(), self.start_cache.as_ref()) {
+            cache.restore(start_cache);
+        }
         self.instruction_pointer = 0;
+        self.relative_base = 0;
         self.noun = noun;
         self.verb = verb;
         self.restore_gravity_assist_program();
     }
 
     fn restore_gravity_assist_program(&mut self) {
-        self.computed_values[1] = self.noun;
-        self.computed_values[2] = self.verb;
+        // Addresses 1 and 2 are always within the memory limit
+        self.write_memory(1, self.noun).unwrap();
+        self.write_memory(2, self.verb).unwrap();
     }
 
-    fn format_instructions(&mut self, instruction: i32) -> i32 {
-        if instruction.to_string().len() == 1 {
-            instruction
-        } else {
-            let mut instruction_vec = create_instruction_vec(&instruction);
-            let opcode = get_opcode_from_instruction_vec(&mut instruction_vec);
-            let formatted_instruction_vec = format_remaining_params(instruction_vec);
-
-            self.params = Some(formatted_instruction_vec);
-            opcode
-        }
+    // Memory past the end of the program reads as 0
+    fn read_memory(&self, address: usize) -> i64 {
+        self.computed_values.get(address).copied().unwrap_or(0)
     }
 
-    fn compute_values(&mut self, num_of_values: usize) {
-        let result_address =
-            self.computed_values[self.instruction_pointer + num_of_values + 1] as usize;
-        self.current_values.address = result_address;
-        let mut current_values = Vec::new();
-
-        if let Some(params_vec) = self.params.clone() {
-            self.compute_values_from_param_modes(params_vec, &num_of_values, &mut current_values);
-
-        //     No params, treat all instructions as indices
-        } else if num_of_values > 0 {
-            self.compute_values_from_positions(&num_of_values, &mut current_values);
+    fn decode(&self, address: usize) -> Result<Instruction, IntcodeErrorKind> {
+        match self.cache.as_ref().and_then(|cache| cache.get(address)) {
+            Some(instruction) => Ok(instruction),
+            None => Instruction::decode(self.read_memory(address)),
         }
-
-        self.current_values.values = Some(current_values);
     }
 
-    fn compute_values_from_param_modes(
-        &mut self,
-        params_vec: Vec<usize>,
-        num_of_values: &usize,
-        current_values: &mut Vec<i32>,
-    ) {
-        let combined_map = combine_params_and_index(params_vec, &num_of_values);
-
-        for (i, param) in combined_map.iter() {
-            if *param == 1 {
-                current_values.push(self.computed_values[self.instruction_pointer + i])
-            } else {
-                current_values.push(
-                    self.computed_values
-                        [self.computed_values[self.instruction_pointer + i] as usize],
-                )
-            };
+    // Each parameter is either an address, the value itself or an offset from the relative base,
+    // depending on its mode
+    fn read_param(&self, instruction: &Instruction, param: usize) -> Result<i64, IntcodeErrorKind> {
+        let value = self.read_memory(self.instruction_pointer + param + 1);
+        match instruction.modes[param] {
+            ParamMode::Position => Ok(self.read_memory(to_address(value)?)),
+            ParamMode::Immediate => Ok(value),
+            ParamMode::Relative => {
+                Ok(self.read_memory(to_address(checked(self.relative_base.checked_add(value))?)?))
+            }
         }
     }
 
-    fn compute_values_from_positions(
+    // Parameters that are written to are always addresses, so can't be in immediate mode
+    fn write_param(
         &mut self,
-        num_of_values: &usize,
-        current_values: &mut Vec<i32>,
-    ) {
-        for i in (1..=*num_of_values).into_iter() {
-            current_values.push(
-                self.computed_values[self.computed_values[self.instruction_pointer + i] as usize],
-            );
-        }
+        instruction: &Instruction,
+        param: usize,
+        value: i64,
+    ) -> Result<(), IntcodeErrorKind> {
+        let param_value = self.read_memory(self.instruction_pointer + param + 1);
+        let address = match instruction.modes[param] {
+            ParamMode::Position => param_value,
+            ParamMode::Immediate => return Err(IntcodeErrorKind::ImmediateModeWrite),
+            ParamMode::Relative => checked(self.relative_base.checked_add(param_value))?,
+        };
+        let address = to_address(address)?;
+        self.write_memory(address, value)?;
+        self.last_write = Some((address, value));
+        Ok(())
     }
 }
 
-fn create_instruction_vec(instruction: &i32) -> Vec<usize> {
-    instruction
-        .to_string()
-        .chars()
-        .map(|d| d.to_digit(10).unwrap() as usize)
-        .collect::<Vec<usize>>()
+// Kept out of ComputerActions so memory only changes as the program runs or is reset, which
+// keeps the instruction cache in step with it
+impl Computer {
+    // Writing past the end of the program grows memory to fit, filling the gap with 0s, up to
+    // the memory limit
+    fn write_memory(&mut self, address: usize, value: i64) -> Result<(), IntcodeErrorKind> {
+        if address >= MEMORY_LIMIT {
+            return Err(IntcodeErrorKind::MemoryLimit(address));
+        }
+        if address >= self.computed_values.len() {
+            self.computed_values.resize(address + 1, 0);
+        }
+        self.computed_values[address] = value;
+        if let Some(cache) = self.cache.as_mut() {
+            cache.invalidate(address);
+        }
+        Ok(())
+    }
 }
 
-fn get_opcode_from_instruction_vec(instruction_vec: &mut Vec<usize>) -> i32 {
-    let vec_len = instruction_vec.len();
-    let opcode_vec: Vec<usize> = instruction_vec.drain(vec_len - 2..vec_len).collect();
-    let opcode = opcode_vec[0].to_string() + &opcode_vec[1].to_string();
-    opcode.parse::<i32>().unwrap()
+// The result of checked arithmetic, with overflow as a fault rather than a panic
+fn checked(value: Option<i64>) -> Result<i64, IntcodeErrorKind> {
+    value.ok_or(IntcodeErrorKind::Overflow)
 }
 
-fn format_remaining_params(instruction_vec: Vec<usize>) -> Vec<usize> {
-    let mut mutable_vec = instruction_vec;
-    mutable_vec.reverse();
-    if mutable_vec.len() != 4 {
-        let additional_zeros = 4 - mutable_vec.len();
-        let mut zeros_vec: Vec<usize> = vec![0; additional_zeros];
-        mutable_vec.append(&mut zeros_vec);
+fn to_address(value: i64) -> Result<usize, IntcodeErrorKind> {
+    if value < 0 {
+        Err(IntcodeErrorKind::NegativeAddress(value))
+    } else {
+        Ok(value as usize)
     }
-    mutable_vec
-}
-
-fn combine_params_and_index(params_vec: Vec<usize>, num_of_values: &usize) -> Vec<(usize, usize)> {
-    (1..=*num_of_values)
-        .collect::<Vec<usize>>()
-        .into_iter()
-        .zip(params_vec.into_iter())
-        .collect()
 }
 
 pub trait ComputerActions {
-    fn new(noun: i32, verb: i32, start_input: Vec<i32>) -> Computer;
-    fn write(&mut self, value: i32, action: &str);
-    fn run(&mut self);
-    fn reset(&mut self, noun: i32, verb: i32);
+    fn new(noun: i64, verb: i64, start_input: Vec<i64>) -> Self;
+    fn write(&mut self, value: i64, action: &str);
+    fn instruction_pointer(&self) -> usize;
+    fn relative_base(&self) -> i64;
+    fn last_write(&self) -> Option<(usize, i64)>;
+    fn memory(&self) -> &[i64];
+    fn start_input(&self) -> &[i64];
+    fn set_start_input(&mut self, start_input: Vec<i64>);
+    fn enable_instruction_cache(&mut self);
+    fn set_tracer(&mut self, tracer: Box<dyn Tracer>);
+    fn push_input(&mut self, value: i64);
+    fn set_input(&mut self, input: Box<dyn Input>);
+    fn set_output(&mut self, output: Box<dyn Output>);
+    fn read_input(&mut self) -> Option<i64>;
+    fn emit_output(&mut self, value: i64);
+    fn step(&mut self) -> Status;
+    fn trace_entry(&self) -> Option<TraceEntry>;
+    fn execute(&mut self) -> Result<Status, IntcodeErrorKind>;
+    fn run_until(&mut self) -> Status;
+    fn run(&mut self) -> Status;
+    fn run_to_halt(&mut self) -> Result<(), IntcodeError>;
+    fn snapshot(&self) -> Snapshot;
+    fn restore(&mut self, snapshot: &Snapshot);
+    fn reset(&mut self, noun: i64, verb: i64);
     fn restore_gravity_assist_program(&mut self);
-    fn format_instructions(&mut self, instruction: i32) -> i32;
-    fn compute_values(&mut self, num_of_values: usize);
-    fn compute_values_from_param_modes(
-        &mut self,
-        params_vec: Vec<usize>,
-        num_of_values: &usize,
-        current_values: &mut Vec<i32>,
-    );
-    fn compute_values_from_positions(
+    fn read_memory(&self, address: usize) -> i64;
+    fn decode(&self, address: usize) -> Result<Instruction, IntcodeErrorKind>;
+    fn read_param(&self, instruction: &Instruction, param: usize) -> Result<i64, IntcodeErrorKind>;
+    fn write_param(
         &mut self,
-        num_of_values: &usize,
-        current_values: &mut Vec<i32>,
-    );
+        instruction: &Instruction,
+        param: usize,
+        value: i64,
+    ) -> Result<(), IntcodeErrorKind>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_parameter_modes() {
+        let mut computer = Computer::new(0, 0, vec![1002, 4, 3, 4, 33]);
+        assert_eq!(computer.run(), Status::Halted);
+        assert_eq!(computer.computed_values, vec![1002, 4, 3, 4, 99]);
+    }
+
+    #[test]
+    fn supports_relative_mode_and_large_numbers() {
+        let quine = vec![
+            109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+        ];
+        let mut computer = Computer::new(0, 0, quine.clone());
+        assert_eq!(computer.run(), Status::Halted);
+        assert_eq!(computer.outputs, quine);
+
+        let mut computer = Computer::new(0, 0, vec![1102, 34915192, 34915192, 7, 4, 7, 99, 0]);
+        computer.run();
+        assert_eq!(computer.outputs, vec![1219070632396864]);
+    }
+
+    #[test]
+    fn grows_memory_when_writing_past_the_program() {
+        let mut computer = Computer::new(0, 0, vec![1101, 1, 2, 10, 4, 10, 99]);
+        computer.run();
+        assert_eq!(computer.outputs, vec![3]);
+        assert_eq!(computer.computed_values.len(), 11);
+    }
+
+    #[test]
+    fn reports_negative_addresses() {
+        let mut computer = Computer::new(0, 0, vec![4, -1, 99]);
+        assert_eq!(
+            computer.run(),
+            Status::Error(IntcodeError {
+                kind: IntcodeErrorKind::NegativeAddress(-1),
+                instruction_pointer: 0,
+                instruction: 4,
+            })
+        );
+    }
+
+    #[test]
+    fn reports_overflow() {
+        let programs = vec![
+            vec![1101, i64::MAX, 1, 0, 99],
+            vec![1102, i64::MAX, 2, 0, 99],
+            vec![109, i64::MAX, 109, 1, 99],
+            vec![109, i64::MAX, 204, 1, 99],
+        ];
+        for program in programs {
+            let mut computer = Computer::new(0, 0, program);
+            assert_eq!(
+                computer.run_to_halt().unwrap_err().kind,
+                IntcodeErrorKind::Overflow
+            );
+        }
+    }
+
+    #[test]
+    fn reports_writes_past_the_memory_limit() {
+        let mut computer = Computer::new(0, 0, vec![1101, 1, 2, i64::MAX, 99]);
+        let error = computer.run_to_halt().unwrap_err();
+        assert_eq!(error.kind, IntcodeErrorKind::MemoryLimit(i64::MAX as usize));
+        assert_eq!(computer.computed_values.len(), 5);
+    }
+
+    #[test]
+    fn traces_executed_instructions() {
+        let log = trace::TraceLog::new();
+        let mut computer = Computer::new(0, 0, vec![3, 0, 1002, 0, 3, 9, 4, 9, 99, 0]);
+        computer.set_tracer(Box::new(log.clone()));
+        assert_eq!(computer.run(), Status::NeedsInput);
+        assert!(log.entries().is_empty());
+
+        computer.push_input(5);
+        computer.run();
+        let trace: Vec<String> = log.take().iter().map(|entry| entry.to_string()).collect();
+        assert_eq!(
+            trace,
+            vec![
+                "     0  IN -> [0] = 5",
+                "     2  MUL 5, 3 -> [9] = 15",
+                "     6  OUT 15",
+                "     8  HLT",
+            ]
+        );
+    }
+
+    #[test]
+    fn echoes_pushed_input() {
+        let mut computer = Computer::new(0, 0, vec![3, 0, 4, 0, 99]);
+        computer.push_input(42);
+        computer.run();
+        assert_eq!(computer.outputs, vec![42]);
+    }
+
+    #[test]
+    fn reads_from_input_source_once_queue_is_empty() {
+        let mut computer = Computer::new(0, 0, vec![3, 9, 3, 10, 4, 9, 4, 10, 99, 0, 0]);
+        computer.push_input(1);
+        computer.set_input(Box::new(vec![2].into_iter().collect::<VecDeque<i64>>()));
+        computer.run();
+        assert_eq!(computer.outputs, vec![1, 2]);
+    }
+
+    #[test]
+    fn stops_on_input_instruction_until_input_is_available() {
+        let mut computer = Computer::new(0, 0, vec![3, 0, 4, 0, 99]);
+        assert_eq!(computer.run(), Status::NeedsInput);
+        assert!(computer.outputs.is_empty());
+        computer.push_input(7);
+        assert_eq!(computer.run(), Status::Halted);
+        assert_eq!(computer.outputs, vec![7]);
+    }
+
+    #[test]
+    fn steps_one_instruction_at_a_time() {
+        let mut computer = Computer::new(0, 0, vec![1101, 2, 3, 0, 104, 9, 99]);
+        assert_eq!(computer.step(), Status::Running);
+        assert_eq!(computer.computed_values[0], 5);
+        assert_eq!(computer.step(), Status::Output(9));
+        assert_eq!(computer.step(), Status::Halted);
+        assert_eq!(computer.step(), Status::Halted);
+    }
+
+    #[test]
+    fn run_until_pauses_on_each_output() {
+        let mut computer = Computer::new(0, 0, vec![104, 1, 104, 2, 99]);
+        assert_eq!(computer.run_until(), Status::Output(1));
+        assert_eq!(computer.run_until(), Status::Output(2));
+        assert_eq!(computer.run_until(), Status::Halted);
+    }
+
+    #[test]
+    fn reports_unknown_opcodes() {
+        let mut computer = Computer::new(0, 0, vec![1, 0, 0, 0, 42]);
+        assert_eq!(
+            computer.run(),
+            Status::Error(IntcodeError {
+                kind: IntcodeErrorKind::UnknownOpcode(42),
+                instruction_pointer: 4,
+                instruction: 42,
+            })
+        );
+    }
+
+    #[test]
+    fn reports_writes_in_immediate_mode() {
+        let mut computer = Computer::new(0, 0, vec![11101, 1, 2, 0, 99]);
+        let error = computer.run_to_halt().unwrap_err();
+        assert_eq!(error.kind, IntcodeErrorKind::ImmediateModeWrite);
+        assert_eq!(
+            error.to_string(),
+            "Write in immediate mode at address 0 (instruction 11101)"
+        );
+    }
+
+    #[test]
+    fn reports_invalid_parameter_modes() {
+        let mut computer = Computer::new(0, 0, vec![301, 1, 2, 0, 99]);
+        assert_eq!(
+            computer.run_to_halt().unwrap_err().kind,
+            IntcodeErrorKind::InvalidParamMode(3)
+        );
+    }
+
+    #[test]
+    fn missing_input_is_a_fault_when_running_to_halt() {
+        let mut computer = Computer::new(0, 0, vec![3, 0, 99]);
+        assert_eq!(
+            computer.run_to_halt().unwrap_err().kind,
+            IntcodeErrorKind::MissingInput
+        );
+    }
 }
@@ -0,0 +1,225 @@
+// Chains copies of a program together so each one's outputs become the next one's inputs.
+// Every amplifier is given its phase setting as its first input, and the first amplifier is
+// then given the input signal. With a feedback loop, the last amplifier's outputs go back into
+// the first, and the chain keeps running until the last amplifier halts.
+use super::{Computer, ComputerActions, Status};
+use crate::error::{Error, IntcodeError, IntcodeErrorKind};
+use std::thread;
+
+pub struct Pipeline {
+    amplifiers: Vec<Computer>,
+    feedback: bool,
+}
+
+impl Pipeline {
+    pub fn new(computer: &Computer, phases: &[i64], feedback: bool) -> Self {
+        let amplifiers = phases
+            .iter()
+            .map(|phase| {
+                let mut amplifier = computer.clone();
+                amplifier.push_input(*phase);
+                amplifier
+            })
+            .collect();
+        Pipeline {
+            amplifiers,
+            feedback,
+        }
+    }
+
+    /// Sends the signal into the first amplifier and returns the last value output by the last
+    /// amplifier once it halts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use advent_of_code::computer::amplifier::Pipeline;
+    /// use advent_of_code::computer::{Computer, ComputerActions};
+    /// let program = vec![3, 15, 3, 16, 1002, 16, 10, 16, 1, 16, 15, 15, 4, 15, 99, 0, 0];
+    /// let mut pipeline = Pipeline::new(&Computer::new(0, 0, program), &[4, 3, 2, 1, 0], false);
+    ///
+    /// assert_eq!(pipeline.run(0).unwrap(), 43210);
+    /// ```
+    pub fn run(&mut self, signal: i64) -> Result<i64, Error> {
+        let count = self.amplifiers.len();
+        if count == 0 {
+            return Err(Error::Custom(String::from(
+                "The pipeline has no amplifiers",
+            )));
+        }
+        self.amplifiers[0].push_input(signal);
+        let mut halted = vec![false; count];
+        let mut last_output = None;
+
+        loop {
+            let mut passed_on_signal = false;
+            for (i, done) in halted.iter_mut().enumerate() {
+                while !*done {
+                    match self.amplifiers[i].run_until() {
+                        Status::Output(value) => {
+                            passed_on_signal = true;
+                            if i + 1 < count {
+                                self.amplifiers[i + 1].push_input(value);
+                            } else {
+                                last_output = Some(value);
+                                if self.feedback {
+                                    self.amplifiers[0].push_input(value);
+                                }
+                            }
+                        }
+                        Status::Halted => *done = true,
+                        Status::Error(error) => return Err(error.into()),
+                        Status::NeedsInput | Status::Running => break,
+                    }
+                }
+            }
+
+            if halted[count - 1] {
+                return last_output.ok_or_else(|| {
+                    Error::Custom(String::from("The last amplifier halted without an output"))
+                });
+            }
+            // Every amplifier is waiting on one that has nothing left to send
+            if !passed_on_signal {
+                let last = &self.amplifiers[count - 1];
+                return Err(IntcodeError {
+                    kind: IntcodeErrorKind::MissingInput,
+                    instruction_pointer: last.instruction_pointer(),
+                    instruction: last.read_memory(last.instruction_pointer()),
+                }
+                .into());
+            }
+        }
+    }
+}
+
+// The best ordering of phase settings found so far and the signal it gave
+type Best = Option<(Vec<i64>, i64)>;
+
+/// Tries every ordering of the phase settings, spread over threads, and returns the ordering
+/// that gives the highest signal from the last amplifier along with that signal.
+pub fn max_thruster_signal(
+    computer: &Computer,
+    phases: &[i64],
+    feedback: bool,
+) -> Result<(Vec<i64>, i64), Error> {
+    let orderings = permutations(phases);
+    let threads = thread::available_parallelism()
+        .map(|threads| threads.get())
+        .unwrap_or(1);
+    let chunk_size = orderings.len().div_ceil(threads);
+
+    let results: Vec<Result<Best, Error>> = thread::scope(|scope| {
+        let searches: Vec<_> = orderings
+            .chunks(chunk_size.max(1))
+            .map(|chunk| {
+                let computer = computer.clone();
+                scope.spawn(move || {
+                    let mut best: Best = None;
+                    for ordering in chunk {
+                        let signal = Pipeline::new(&computer, ordering, feedback).run(0)?;
+                        if beats(&best, signal) {
+                            best = Some((ordering.clone(), signal));
+                        }
+                    }
+                    Ok(best)
+                })
+            })
+            .collect();
+        searches
+            .into_iter()
+            .map(|search| search.join().expect("Search thread panicked"))
+            .collect()
+    });
+
+    let mut best: Best = None;
+    for result in results {
+        if let Some((ordering, signal)) = result? {
+            if beats(&best, signal) {
+                best = Some((ordering, signal));
+            }
+        }
+    }
+    best.ok_or_else(|| Error::Custom(String::from("No phase settings to try")))
+}
+
+fn beats(best: &Best, signal: i64) -> bool {
+    match best {
+        Some((_, max)) => signal > *max,
+        None => true,
+    }
+}
+
+// Every ordering of the values, using Heap's algorithm
+fn permutations(values: &[i64]) -> Vec<Vec<i64>> {
+    let mut values = values.to_vec();
+    let mut counters = vec![0; values.len()];
+    let mut orderings = vec![values.clone()];
+    let mut i = 0;
+    while i < values.len() {
+        if counters[i] < i {
+            if i % 2 == 0 {
+                values.swap(0, i);
+            } else {
+                values.swap(counters[i], i);
+            }
+            orderings.push(values.clone());
+            counters[i] += 1;
+            i = 0;
+        } else {
+            counters[i] = 0;
+            i += 1;
+        }
+    }
+    orderings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_best_phase_settings() {
+        let program = vec![
+            3, 23, 3, 24, 1002, 24, 10, 24, 1002, 23, -1, 23, 101, 5, 23, 23, 1, 24, 23, 23, 4, 23,
+            99, 0, 0,
+        ];
+        let computer = Computer::new(0, 0, program);
+        assert_eq!(
+            max_thruster_signal(&computer, &[0, 1, 2, 3, 4], false).unwrap(),
+            (vec![0, 1, 2, 3, 4], 54321)
+        );
+    }
+
+    #[test]
+    fn runs_a_feedback_loop() {
+        let program = vec![
+            3, 26, 1001, 26, -4, 26, 3, 27, 1002, 27, 2, 27, 1, 27, 26, 27, 4, 27, 1001, 28, -1,
+            28, 1005, 28, 6, 99, 0, 0, 5,
+        ];
+        let computer = Computer::new(0, 0, program);
+        let mut pipeline = Pipeline::new(&computer, &[9, 8, 7, 6, 5], true);
+        assert_eq!(pipeline.run(0).unwrap(), 139629729);
+        assert_eq!(
+            max_thruster_signal(&computer, &[5, 6, 7, 8, 9], true).unwrap(),
+            (vec![9, 8, 7, 6, 5], 139629729)
+        );
+    }
+
+    #[test]
+    fn generates_every_permutation() {
+        let mut orderings = permutations(&[1, 2, 3]);
+        orderings.sort();
+        assert_eq!(
+            orderings,
+            vec![
+                vec![1, 2, 3],
+                vec![1, 3, 2],
+                vec![2, 1, 3],
+                vec![2, 3, 1],
+                vec![3, 1, 2],
+                vec![3, 2, 1],
+            ]
+        );
+    }
+}
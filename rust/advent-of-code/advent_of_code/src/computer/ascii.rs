@@ -0,0 +1,237 @@
+// Talks to Intcode programs that speak ASCII: they print text one character per output and
+// read commands one character per input, ending each one with a newline. Any output that isn't
+// an ASCII character (usually a large number at the end of the program) is passed through as
+// a value instead of text.
+//
+// In a session, commands starting with `!` are handled by the adapter rather than sent to the
+// program: `!history` lists the commands sent so far, `!!` repeats the last one and `!n`
+// repeats command n.
+use super::{Computer, ComputerActions, Status};
+use crate::error::Error;
+use std::convert::TryFrom;
+use std::io::{self, BufRead, Write};
+
+const NEWLINE: i64 = b'\n' as i64;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Reply {
+    /// A line of text, without its newline.
+    Line(String),
+    /// An output that isn't an ASCII character.
+    Value(i64),
+}
+
+/// Converts a command into the inputs for it, ending with a newline.
+///
+/// # Examples
+///
+/// ```
+/// use advent_of_code::computer::ascii::encode;
+///
+/// assert_eq!(encode("inv"), vec![105, 110, 118, 10]);
+/// ```
+pub fn encode(command: &str) -> Vec<i64> {
+    command
+        .bytes()
+        .map(i64::from)
+        .chain(std::iter::once(NEWLINE))
+        .collect()
+}
+
+pub struct Ascii {
+    computer: Computer,
+    // Text output since the last newline
+    partial_line: String,
+    history: Vec<String>,
+}
+
+impl Ascii {
+    pub fn new(computer: Computer) -> Self {
+        Ascii {
+            computer,
+            partial_line: String::new(),
+            history: Vec::new(),
+        }
+    }
+
+    pub fn computer(&self) -> &Computer {
+        &self.computer
+    }
+
+    pub fn computer_mut(&mut self) -> &mut Computer {
+        &mut self.computer
+    }
+
+    /// The commands sent so far, oldest first.
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    /// Queues a command for the program.
+    pub fn send(&mut self, command: &str) {
+        for value in encode(command) {
+            self.computer.push_input(value);
+        }
+        self.history.push(command.to_string());
+    }
+
+    /// Runs until the program halts, faults or needs another command, returning what it
+    /// printed. A line the program hasn't finished, like a prompt, is returned as it is.
+    pub fn run(&mut self) -> (Vec<Reply>, Status) {
+        let mut replies = Vec::new();
+        loop {
+            match self.computer.run_until() {
+                Status::Output(NEWLINE) => {
+                    replies.push(Reply::Line(self.partial_line.split_off(0)));
+                }
+                Status::Output(value) => match ascii_char(value) {
+                    Some(c) => self.partial_line.push(c),
+                    None => replies.push(Reply::Value(value)),
+                },
+                status => {
+                    if !self.partial_line.is_empty() {
+                        replies.push(Reply::Line(self.partial_line.split_off(0)));
+                    }
+                    return (replies, status);
+                }
+            }
+        }
+    }
+
+    /// Plays the program, reading commands from input until it runs out or the program halts.
+    /// With `echo` set, each command is printed as it's sent, which makes scripted sessions
+    /// easier to follow.
+    pub fn session<R: BufRead, W: Write>(
+        &mut self,
+        input: R,
+        output: &mut W,
+        echo: bool,
+    ) -> Result<Status, Error> {
+        let mut lines = input.lines();
+        loop {
+            let (replies, status) = self.run();
+            for reply in replies {
+                match reply {
+                    Reply::Line(line) => writeln!(output, "{}", line)?,
+                    Reply::Value(value) => writeln!(output, "Value: {}", value)?,
+                }
+            }
+            match status {
+                Status::NeedsInput => {}
+                Status::Error(error) => return Err(error.into()),
+                status => return Ok(status),
+            }
+
+            // Keep reading until there's a command for the program
+            loop {
+                let line = match lines.next() {
+                    Some(line) => line?,
+                    None => return Ok(Status::NeedsInput),
+                };
+                if let Some(command) = self.command(line.trim(), output)? {
+                    if echo {
+                        writeln!(output, "{}", command)?;
+                    }
+                    self.send(&command);
+                    break;
+                }
+            }
+        }
+    }
+
+    // Returns the command to send, or None if the line was handled here
+    fn command<W: Write>(&self, line: &str, output: &mut W) -> io::Result<Option<String>> {
+        let repeat = match line.strip_prefix('!') {
+            Some(repeat) => repeat,
+            None => return Ok(Some(line.to_string())),
+        };
+        let command = match repeat {
+            "history" => {
+                for (i, command) in self.history.iter().enumerate() {
+                    writeln!(output, "{:>4}  {}", i + 1, command)?;
+                }
+                return Ok(None);
+            }
+            "!" => self.history.last(),
+            number => number
+                .parse::<usize>()
+                .ok()
+                .and_then(|number| self.history.get(number.checked_sub(1)?)),
+        };
+        if command.is_none() {
+            writeln!(output, "No command {} in the history", line)?;
+        }
+        Ok(command.cloned())
+    }
+}
+
+fn ascii_char(value: i64) -> Option<char> {
+    u8::try_from(value)
+        .ok()
+        .filter(|byte| byte.is_ascii())
+        .map(char::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::computer::assembler::assemble;
+    use std::io::Cursor;
+
+    // Prints a prompt, then echoes each character it's given back in upper case until the end
+    // of the line, then outputs 1000 plus the line's length as a value
+    const SHOUT: &str = "
+                OUT #62
+                OUT #10
+        loop:   IN [char]
+                EQ [char], #10, [tmp]
+                JNZ [tmp], #done
+                ADD [char], #-32, [char]
+                OUT [char]
+                ADD [length], #1, [length]
+                JZ #0, #loop
+        done:   OUT #10
+                OUT [length]
+                HLT
+        char:   DATA 0
+        tmp:    DATA 0
+        length: DATA 1000
+    ";
+
+    fn shout() -> Ascii {
+        Ascii::new(Computer::new(0, 0, assemble(SHOUT).unwrap()))
+    }
+
+    #[test]
+    fn splits_text_into_lines_and_passes_values_through() {
+        let mut ascii = shout();
+        assert_eq!(
+            ascii.run(),
+            (vec![Reply::Line(String::from(">"))], Status::NeedsInput)
+        );
+        ascii.send("hello");
+        assert_eq!(
+            ascii.run(),
+            (
+                vec![Reply::Line(String::from("HELLO")), Reply::Value(1005)],
+                Status::Halted
+            )
+        );
+    }
+
+    #[test]
+    fn repeats_commands_from_the_history() {
+        let mut ascii = shout();
+        ascii.history.push(String::from("abc"));
+        let mut output = Vec::new();
+        let status = ascii
+            .session(Cursor::new("!2\n!history\n!!\n"), &mut output, true)
+            .unwrap();
+        assert_eq!(status, Status::Halted);
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            ">\nNo command !2 in the history\n   1  abc\nabc\nABC\nValue: 1003\n"
+        );
+        assert_eq!(ascii.history(), ["abc", "abc"]);
+    }
+}
@@ -0,0 +1,262 @@
+// A small assembly language for writing Intcode programs by hand. It uses the same syntax the
+// disassembler prints, plus labels and comments:
+//
+//     ; count down from 3, outputting each number
+//             ADD #3, #0, [counter]
+//     loop:   OUT [counter]
+//             ADD [counter], #-1, [counter]
+//             JNZ [counter], #loop
+//             HLT
+//     counter: DATA 0
+//
+// Operands are [12] for position mode, #5 for immediate mode and [rb+3] / [rb-3] for relative mode.
+// A label can be used anywhere a number can, and stands for the address it was declared at.
+// `DATA` writes its comma separated values straight into the program.
+use super::instruction::{Instruction, Opcode, ParamMode};
+use crate::error::Error;
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Value {
+    Number(i64),
+    Label(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Item {
+    Instruction(Opcode, Vec<(ParamMode, Value)>),
+    Data(Vec<Value>),
+}
+
+/// Assembles source into a program the `Computer` can load.
+///
+/// # Examples
+///
+/// ```
+/// use advent_of_code::computer::assembler::assemble;
+/// let program = assemble("MUL [4], #3, [4]\nDATA 33").unwrap();
+///
+/// assert_eq!(program, vec![1002, 4, 3, 4, 33]);
+/// ```
+pub fn assemble(source: &str) -> Result<Vec<i64>, Error> {
+    let mut labels: HashMap<String, i64> = HashMap::new();
+    let mut items: Vec<(usize, Item)> = Vec::new();
+    let mut address = 0;
+
+    // First pass: parse each line and work out the address of every label
+    for (i, line) in source.lines().enumerate() {
+        let line_number = i + 1;
+        let mut statement = strip_comment(line).trim();
+        while let Some((label, rest)) = split_label(statement) {
+            if labels.insert(label.to_string(), address).is_some() {
+                return Err(assembly_error(
+                    line_number,
+                    &format!("Label {} is declared more than once", label),
+                ));
+            }
+            statement = rest;
+        }
+        if statement.is_empty() {
+            continue;
+        }
+        let item =
+            parse_statement(statement).map_err(|message| assembly_error(line_number, &message))?;
+        address += match &item {
+            Item::Instruction(opcode, _) => opcode.param_count() as i64 + 1,
+            Item::Data(values) => values.len() as i64,
+        };
+        items.push((line_number, item));
+    }
+
+    // Second pass: encode everything now that all of the labels are known
+    let mut program = Vec::new();
+    for (line_number, item) in items {
+        let resolve = |value: &Value| match value {
+            Value::Number(number) => Ok(*number),
+            Value::Label(label) => labels
+                .get(label)
+                .copied()
+                .ok_or_else(|| assembly_error(line_number, &format!("Unknown label {}", label))),
+        };
+        match item {
+            Item::Instruction(opcode, operands) => {
+                let mut modes = [ParamMode::Position; 3];
+                for (mode, (operand_mode, _)) in modes.iter_mut().zip(operands.iter()) {
+                    *mode = *operand_mode;
+                }
+                program.push(Instruction { opcode, modes }.encode());
+                for (_, value) in operands.iter() {
+                    program.push(resolve(value)?);
+                }
+            }
+            Item::Data(values) => {
+                for value in values.iter() {
+                    program.push(resolve(value)?);
+                }
+            }
+        }
+    }
+    Ok(program)
+}
+
+fn assembly_error(line_number: usize, message: &str) -> Error {
+    Error::Custom(format!(
+        "Assembly error on line {}: {}",
+        line_number, message
+    ))
+}
+
+fn strip_comment(line: &str) -> &str {
+    match line.find(';') {
+        Some(index) => &line[..index],
+        None => line,
+    }
+}
+
+// A statement can start with any number of `label:` declarations
+fn split_label(statement: &str) -> Option<(&str, &str)> {
+    let index = statement.find(':')?;
+    let label = statement[..index].trim();
+    if is_label(label) {
+        Some((label, statement[index + 1..].trim()))
+    } else {
+        None
+    }
+}
+
+fn is_label(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) if first.is_ascii_alphabetic() || first == '_' => {
+            chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        _ => false,
+    }
+}
+
+fn parse_statement(statement: &str) -> Result<Item, String> {
+    let (mnemonic, rest) = match statement.find(char::is_whitespace) {
+        Some(index) => (&statement[..index], statement[index..].trim()),
+        None => (statement, ""),
+    };
+    let arguments: Vec<&str> = if rest.is_empty() {
+        Vec::new()
+    } else {
+        rest.split(',').map(|argument| argument.trim()).collect()
+    };
+
+    if mnemonic.eq_ignore_ascii_case("DATA") {
+        if arguments.is_empty() {
+            return Err(String::from("DATA needs at least one value"));
+        }
+        let values = arguments
+            .iter()
+            .map(|argument| parse_value(argument))
+            .collect::<Result<Vec<Value>, String>>()?;
+        return Ok(Item::Data(values));
+    }
+
+    let opcode =
+        Opcode::from_mnemonic(mnemonic).ok_or_else(|| format!("Unknown mnemonic {}", mnemonic))?;
+    if arguments.len() != opcode.param_count() {
+        return Err(format!(
+            "{} takes {} operands but was given {}",
+            opcode.mnemonic(),
+            opcode.param_count(),
+            arguments.len()
+        ));
+    }
+    let operands = arguments
+        .iter()
+        .map(|argument| parse_operand(argument))
+        .collect::<Result<Vec<(ParamMode, Value)>, String>>()?;
+    Ok(Item::Instruction(opcode, operands))
+}
+
+fn parse_operand(operand: &str) -> Result<(ParamMode, Value), String> {
+    if let Some(value) = operand.strip_prefix('#') {
+        return Ok((ParamMode::Immediate, parse_value(value.trim())?));
+    }
+    let inner = operand
+        .strip_prefix('[')
+        .and_then(|operand| operand.strip_suffix(']'))
+        .map(|inner| inner.trim())
+        .ok_or_else(|| format!("Invalid operand {}", operand))?;
+    // [rb+3] and [rb-3] are relative, anything else in brackets is a position
+    match inner.strip_prefix("rb").map(|offset| offset.trim_start()) {
+        Some(offset) if offset.starts_with('+') => {
+            Ok((ParamMode::Relative, parse_value(offset[1..].trim())?))
+        }
+        Some(offset) if offset.starts_with('-') => Ok((ParamMode::Relative, parse_value(offset)?)),
+        _ => Ok((ParamMode::Position, parse_value(inner)?)),
+    }
+}
+
+fn parse_value(value: &str) -> Result<Value, String> {
+    if is_label(value) {
+        Ok(Value::Label(value.to_string()))
+    } else {
+        value
+            .parse::<i64>()
+            .map(Value::Number)
+            .map_err(|_| format!("Invalid value {}", value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::computer::disassembler;
+    use crate::computer::{Computer, ComputerActions};
+
+    #[test]
+    fn resolves_labels() {
+        let source = "
+            ; count down from 3, outputting each number
+                    ADD #3, #0, [counter]
+            loop:   OUT [counter]
+                    ADD [counter], #-1, [counter]
+                    JNZ [counter], #loop
+                    HLT
+            counter: DATA 0
+        ";
+        let program = assemble(source).unwrap();
+        assert_eq!(
+            program,
+            vec![1101, 3, 0, 14, 4, 14, 1001, 14, -1, 14, 1005, 14, 4, 99, 0]
+        );
+
+        let mut computer = Computer::new(0, 0, program);
+        computer.run();
+        assert_eq!(computer.outputs, vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn assembles_relative_operands() {
+        assert_eq!(
+            assemble("ARB #10\nOUT [rb-1]\nIN [rb+2]").unwrap(),
+            vec![109, 10, 204, -1, 203, 2]
+        );
+    }
+
+    #[test]
+    fn round_trips_through_the_disassembler() {
+        let program = vec![3, 9, 1008, 9, 8, 9, 4, 9, 99, -1, 8, 10099, 21107, 1, 2, 3];
+        let source = disassembler::source(&program);
+        assert_eq!(assemble(&source).unwrap(), program);
+    }
+
+    #[test]
+    fn reports_errors_with_line_numbers() {
+        let error = assemble("HLT\nADD #1, #2").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Assembly error on line 2: ADD takes 3 operands but was given 2"
+        );
+        let error = assemble("JNZ #1, #nowhere").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Assembly error on line 1: Unknown label nowhere"
+        );
+    }
+}
@@ -0,0 +1,133 @@
+// Decoding an instruction takes its opcode and three parameter modes out of its value with a
+// remainder and a division for each, checking each one is valid, on every step. Small, but it's
+// most of the work of a step that adds two numbers, and it's repeated for the same instructions
+// when a program loops or is run thousands of times. The cache decodes every address of a program
+// once up front. Writing to an address drops it from the cache, so the Computer decodes whatever
+// is written there as usual and programs that modify themselves still run the instruction that's
+// really there.
+//
+// Addresses that don't hold a valid instruction (data, or anything past the end of memory) are
+// left empty, and the Computer decodes those as usual so it can report the fault. The addresses
+// written to are remembered, so going back to the program as loaded only restores those.
+use super::instruction::Instruction;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InstructionCache {
+    instructions: Vec<Option<Instruction>>,
+    // Addresses dropped from the cache since it was compiled or restored
+    written: Vec<usize>,
+}
+
+impl InstructionCache {
+    pub fn compile(memory: &[i64]) -> Self {
+        InstructionCache {
+            instructions: memory
+                .iter()
+                .map(|value| Instruction::decode(*value).ok())
+                .collect(),
+            written: Vec::new(),
+        }
+    }
+
+    pub fn get(&self, address: usize) -> Option<Instruction> {
+        self.instructions.get(address).copied().flatten()
+    }
+
+    /// Drops an address that has been written to, so it's decoded from memory from now on.
+    pub fn invalidate(&mut self, address: usize) {
+        if let Some(instruction) = self.instructions.get_mut(address) {
+            if instruction.take().is_some() {
+                self.written.push(address);
+            }
+        }
+    }
+
+    /// Goes back to `start`, the cache this one was cloned from, by restoring only the addresses
+    /// written to since.
+    pub fn restore(&mut self, start: &InstructionCache) {
+        for address in self.written.drain(..) {
+            self.instructions[address] = start.instructions[address];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::computer::instruction::Opcode;
+    use crate::computer::{Computer, ComputerActions, Status};
+
+    #[test]
+    fn decodes_every_instruction_up_front() {
+        let cache = InstructionCache::compile(&[1002, 4, 3, 4, 33, 99]);
+        assert_eq!(cache.get(0), Instruction::decode(1002).ok());
+        assert_eq!(cache.get(4), None);
+        assert_eq!(cache.get(5).map(|i| i.opcode), Some(Opcode::Halt));
+        assert_eq!(cache.get(6), None);
+    }
+
+    #[test]
+    fn restores_only_what_was_written() {
+        let start = InstructionCache::compile(&[1002, 4, 3, 4, 33, 99]);
+        let mut cache = start.clone();
+        cache.invalidate(5);
+        cache.invalidate(4);
+        cache.invalidate(100);
+        assert_eq!(cache.get(5), None);
+        assert_eq!(cache.written, vec![5]);
+        cache.restore(&start);
+        assert_eq!(cache, start);
+    }
+
+    #[test]
+    fn runs_self_modifying_programs() {
+        // Overwrites the 33 at address 4 with 99 (halt) before running it
+        let program = vec![1002, 4, 3, 4, 33];
+        let mut computer = Computer::new(0, 0, program.clone());
+        computer.enable_instruction_cache();
+        assert_eq!(computer.run(), Status::Halted);
+        assert_eq!(computer.memory(), &[1002, 4, 3, 4, 99]);
+
+        // Reads an input and adds it to the halt at address 10 before running it. An input of
+        // -95 turns the halt into an output of address 13, which has been cleared by then.
+        let program = vec![3, 13, 1, 13, 10, 10, 1101, 0, 0, 13, 99, 13, 99, 0];
+        for (input, outputs) in &[(-95, vec![0]), (0, vec![])] {
+            let mut computer = Computer::new(0, 0, program.clone());
+            computer.enable_instruction_cache();
+            computer.push_input(*input);
+            computer.run();
+            assert_eq!(&computer.outputs, outputs);
+        }
+    }
+
+    #[test]
+    fn resets_to_the_program_as_loaded() {
+        // Overwrites the add at address 4 with a halt
+        let program = vec![1101, 0, 99, 4, 1];
+        let mut computer = Computer::new(0, 0, program.clone());
+        computer.enable_instruction_cache();
+        assert_eq!(computer.run(), Status::Halted);
+        let cached = |computer: &Computer| computer.cache.as_ref().unwrap().get(4);
+        assert_eq!(cached(&computer), None);
+
+        computer.reset(0, 99);
+        assert_eq!(computer.memory(), &program[..]);
+        assert_eq!(cached(&computer), Instruction::decode(1).ok());
+        assert_eq!(computer.run(), Status::Halted);
+    }
+
+    #[test]
+    fn resets_to_a_new_start_input() {
+        let mut computer = Computer::new(0, 0, vec![1101, 0, 0, 0, 104, 5, 99]);
+        computer.enable_instruction_cache();
+        computer.run();
+        assert_eq!(computer.outputs, vec![5]);
+
+        // The output at address 4 now reads address 0 instead of being immediate
+        computer.set_start_input(vec![1101, 0, 0, 0, 4, 0, 99]);
+        computer.outputs.clear();
+        computer.reset(3, 4);
+        computer.run();
+        assert_eq!(computer.outputs, vec![7]);
+    }
+}
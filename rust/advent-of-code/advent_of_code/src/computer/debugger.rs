@@ -0,0 +1,351 @@
+// Step through an Intcode program with breakpoints on addresses and watchpoints on writes to
+// memory. `session` runs an interactive prompt; type `help` for the list of commands.
+use super::disassembler;
+use super::snapshot::Snapshot;
+use super::trace::TraceLog;
+use super::{Computer, ComputerActions, Status};
+use std::collections::BTreeSet;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+// The most values `memory` prints at once
+const MAX_MEMORY_VALUES: usize = 256;
+
+const HELP: &str = "\
+step [n]              s  Run the next n instructions (default 1), printing each one
+continue              c  Run until a breakpoint, a watchpoint or the program stops
+break [address]       b  Stop before running the instruction at address, or list breakpoints
+watch [address]       w  Stop after address is written to, or list watchpoints
+delete <address>      d  Remove the breakpoint and watchpoint at address
+memory <address> [n]  m  Print n values (default 8, at most 256) starting at address
+input <value>         i  Queue an input for the program
+registers             r  Print the instruction pointer, relative base and next instruction
+list [n]              l  Disassemble the next n instructions (default 5)
+save <file>              Save a snapshot of the machine to file
+load <file>              Restore the machine from a snapshot file
+help                  h  Print this message
+quit                  q  Leave the debugger";
+
+/// Why the debugger handed control back.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Stop {
+    Breakpoint(usize),
+    Watchpoint {
+        address: usize,
+        value: i64,
+    },
+    /// The computer's status after the last instruction, when no breakpoint or watchpoint was hit.
+    Status(Status),
+}
+
+pub struct Debugger {
+    computer: Computer,
+    trace: TraceLog,
+    breakpoints: BTreeSet<usize>,
+    watchpoints: BTreeSet<usize>,
+    reported_outputs: usize,
+}
+
+impl Debugger {
+    pub fn new(mut computer: Computer) -> Self {
+        let trace = TraceLog::new();
+        computer.set_tracer(Box::new(trace.clone()));
+        Debugger {
+            computer,
+            trace,
+            breakpoints: BTreeSet::new(),
+            watchpoints: BTreeSet::new(),
+            reported_outputs: 0,
+        }
+    }
+
+    pub fn computer(&self) -> &Computer {
+        &self.computer
+    }
+
+    pub fn computer_mut(&mut self) -> &mut Computer {
+        &mut self.computer
+    }
+
+    pub fn add_breakpoint(&mut self, address: usize) {
+        self.breakpoints.insert(address);
+    }
+
+    pub fn add_watchpoint(&mut self, address: usize) {
+        self.watchpoints.insert(address);
+    }
+
+    /// Removes any breakpoint or watchpoint at address, returning whether there was one.
+    pub fn delete(&mut self, address: usize) -> bool {
+        let removed_breakpoint = self.breakpoints.remove(&address);
+        let removed_watchpoint = self.watchpoints.remove(&address);
+        removed_breakpoint || removed_watchpoint
+    }
+
+    /// Runs a single instruction.
+    pub fn step(&mut self) -> Stop {
+        let status = self.computer.step();
+        match self.computer.last_write() {
+            Some((address, value)) if self.watchpoints.contains(&address) => {
+                Stop::Watchpoint { address, value }
+            }
+            _ => Stop::Status(status),
+        }
+    }
+
+    /// Runs until a breakpoint or watchpoint is hit, or the program halts, needs an input or
+    /// faults. Outputs don't stop it. At least one instruction always runs, so resuming from a
+    /// breakpoint moves past it.
+    pub fn resume(&mut self) -> Stop {
+        loop {
+            match self.step() {
+                Stop::Status(Status::Running) | Stop::Status(Status::Output(_)) => {}
+                stop => return stop,
+            }
+            let instruction_pointer = self.computer.instruction_pointer();
+            if self.breakpoints.contains(&instruction_pointer) {
+                return Stop::Breakpoint(instruction_pointer);
+            }
+        }
+    }
+
+    /// Reads commands from input until it runs out or `quit` is entered.
+    pub fn session<R: BufRead, W: Write>(&mut self, input: R, output: &mut W) -> io::Result<()> {
+        let mut lines = input.lines();
+        loop {
+            write!(output, "(icdb) ")?;
+            output.flush()?;
+            let line = match lines.next() {
+                Some(line) => line?,
+                None => return Ok(()),
+            };
+            if !self.command(&line, output)? {
+                return Ok(());
+            }
+        }
+    }
+
+    // Runs a single command, returning false once the session should end
+    fn command<W: Write>(&mut self, line: &str, output: &mut W) -> io::Result<bool> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let (command, args) = match words.split_first() {
+            Some((command, args)) => (*command, args),
+            None => return Ok(true),
+        };
+        let number = |index: usize| args.get(index).and_then(|arg| arg.parse::<i64>().ok());
+        let address = |index: usize| args.get(index).and_then(|arg| arg.parse::<usize>().ok());
+
+        match command {
+            "s" | "step" => {
+                for _ in 0..address(0).unwrap_or(1) {
+                    let stop = self.step();
+                    self.report_trace(output)?;
+                    self.report_outputs(output)?;
+                    if !matches!(
+                        stop,
+                        Stop::Status(Status::Running) | Stop::Status(Status::Output(_))
+                    ) {
+                        self.report_stop(&stop, output)?;
+                        break;
+                    }
+                }
+            }
+            "c" | "continue" => {
+                let stop = self.resume();
+                self.trace.take();
+                self.report_outputs(output)?;
+                self.report_stop(&stop, output)?;
+            }
+            "b" | "break" => match address(0) {
+                Some(address) => self.add_breakpoint(address),
+                None => writeln!(output, "Breakpoints: {:?}", self.breakpoints)?,
+            },
+            "w" | "watch" => match address(0) {
+                Some(address) => self.add_watchpoint(address),
+                None => writeln!(output, "Watchpoints: {:?}", self.watchpoints)?,
+            },
+            "d" | "delete" => match address(0) {
+                Some(address) if self.delete(address) => {}
+                _ => writeln!(output, "No breakpoint or watchpoint there")?,
+            },
+            "m" | "memory" => match address(0) {
+                Some(start) => {
+                    let count = address(1).unwrap_or(8);
+                    match start.checked_add(count.min(MAX_MEMORY_VALUES)) {
+                        Some(end) => {
+                            let values: Vec<String> = (start..end)
+                                .map(|address| self.computer.read_memory(address).to_string())
+                                .collect();
+                            writeln!(output, "[{}] {}", start, values.join(", "))?;
+                            if count > MAX_MEMORY_VALUES {
+                                writeln!(output, "Showing the first {} values", MAX_MEMORY_VALUES)?;
+                            }
+                        }
+                        None => writeln!(output, "Addresses past {} don't exist", usize::MAX)?,
+                    }
+                }
+                None => writeln!(output, "Usage: memory <address> [n]")?,
+            },
+            "i" | "input" => match number(0) {
+                Some(value) => self.computer.push_input(value),
+                None => writeln!(output, "Usage: input <value>")?,
+            },
+            "r" | "registers" => {
+                writeln!(
+                    output,
+                    "ip: {}  relative base: {}",
+                    self.computer.instruction_pointer(),
+                    self.computer.relative_base()
+                )?;
+                self.list(1, output)?;
+            }
+            "l" | "list" => self.list(address(0).unwrap_or(5), output)?,
+            "save" => match args.first() {
+                Some(path) => {
+                    if let Err(error) = self.computer.snapshot().save(Path::new(path)) {
+                        writeln!(output, "Error: {}", error)?;
+                    }
+                }
+                None => writeln!(output, "Usage: save <file>")?,
+            },
+            "load" => match args.first().map(|path| Snapshot::load(Path::new(path))) {
+                Some(Ok(snapshot)) => {
+                    self.computer.restore(&snapshot);
+                    self.reported_outputs = self.computer.outputs.len();
+                }
+                Some(Err(error)) => writeln!(output, "Error: {}", error)?,
+                None => writeln!(output, "Usage: load <file>")?,
+            },
+            "h" | "help" => writeln!(output, "{}", HELP)?,
+            "q" | "quit" => return Ok(false),
+            _ => writeln!(output, "Unknown command {}, try `help`", command)?,
+        }
+        Ok(true)
+    }
+
+    fn list<W: Write>(&self, count: usize, output: &mut W) -> io::Result<()> {
+        let start = self.computer.instruction_pointer();
+        let memory = self.computer.memory().get(start..).unwrap_or(&[]);
+        for line in disassembler::disassemble(memory).iter().take(count) {
+            writeln!(output, "{:>6}  {}", start + line.address, line.statement)?;
+        }
+        Ok(())
+    }
+
+    fn report_trace<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        for entry in self.trace.take() {
+            writeln!(output, "{}", entry)?;
+        }
+        Ok(())
+    }
+
+    fn report_outputs<W: Write>(&mut self, output: &mut W) -> io::Result<()> {
+        for value in &self.computer.outputs[self.reported_outputs..] {
+            writeln!(output, "Output: {}", value)?;
+        }
+        self.reported_outputs = self.computer.outputs.len();
+        Ok(())
+    }
+
+    fn report_stop<W: Write>(&self, stop: &Stop, output: &mut W) -> io::Result<()> {
+        match stop {
+            Stop::Breakpoint(address) => writeln!(output, "Breakpoint at address {}", address),
+            Stop::Watchpoint { address, value } => {
+                writeln!(output, "Watchpoint: [{}] = {}", address, value)
+            }
+            Stop::Status(Status::Halted) => writeln!(output, "Program halted"),
+            Stop::Status(Status::NeedsInput) => {
+                writeln!(output, "Waiting for input, queue one with `input <value>`")
+            }
+            Stop::Status(Status::Error(error)) => writeln!(output, "Error: {}", error),
+            Stop::Status(_) => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    // Outputs 3, 2, 1 then halts, with the counter kept at address 14
+    const COUNTDOWN: [i64; 15] = [1101, 3, 0, 14, 4, 14, 1001, 14, -1, 14, 1005, 14, 4, 99, 0];
+
+    fn session(commands: &str) -> Vec<String> {
+        let mut debugger = Debugger::new(Computer::new(0, 0, COUNTDOWN.to_vec()));
+        let mut output = Vec::new();
+        debugger
+            .session(Cursor::new(commands), &mut output)
+            .unwrap();
+        String::from_utf8(output)
+            .unwrap()
+            .split("(icdb) ")
+            .map(|response| response.trim_end().to_string())
+            .filter(|response| !response.is_empty())
+            .collect()
+    }
+
+    #[test]
+    fn stops_at_breakpoints_and_watchpoints() {
+        let mut debugger = Debugger::new(Computer::new(0, 0, COUNTDOWN.to_vec()));
+        debugger.add_breakpoint(10);
+        assert_eq!(debugger.resume(), Stop::Breakpoint(10));
+        assert_eq!(debugger.computer().outputs, vec![3]);
+
+        debugger.delete(10);
+        debugger.add_watchpoint(14);
+        assert_eq!(
+            debugger.resume(),
+            Stop::Watchpoint {
+                address: 14,
+                value: 1
+            }
+        );
+        debugger.delete(14);
+        assert_eq!(debugger.resume(), Stop::Status(Status::Halted));
+        assert_eq!(debugger.computer().outputs, vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn runs_commands_from_a_session() {
+        let responses =
+            session("step 2\nwatch 14\nc\nmemory 12 3\nbreak 13\nc\nc\ndelete 14\nc\nc\nquit\n");
+        assert_eq!(
+            responses,
+            vec![
+                "     0  ADD 3, 0 -> [14] = 3\n     4  OUT 3\nOutput: 3",
+                "Watchpoint: [14] = 2",
+                "[12] 4, 99, 2",
+                "Output: 2\nWatchpoint: [14] = 1",
+                "Output: 1\nWatchpoint: [14] = 0",
+                "Breakpoint at address 13",
+                "Program halted",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_memory_ranges_past_the_last_address() {
+        let responses = session(&format!("memory {} 2\nmemory 15 2\nquit\n", usize::MAX));
+        assert_eq!(
+            responses,
+            vec![
+                format!("Addresses past {} don't exist", usize::MAX),
+                String::from("[15] 0, 0"),
+            ]
+        );
+    }
+
+    #[test]
+    fn caps_how_many_values_memory_prints() {
+        let responses = session(
+            "memory 0 100000000000000
+quit
+",
+        );
+        let lines: Vec<&str> = responses[0].lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].split(", ").count(), MAX_MEMORY_VALUES);
+        assert_eq!(lines[1], "Showing the first 256 values");
+    }
+}
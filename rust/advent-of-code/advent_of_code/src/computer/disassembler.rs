@@ -0,0 +1,188 @@
+// Turns a program back into something readable, one instruction per line:
+//      0  1002,4,3,4  MUL [4], #3, [4]
+//      4  33          DATA 33
+// Operands are written as [12] for position mode, #5 for immediate mode and [rb+3] for relative mode.
+// The program is read front to back, so anything that can't be decoded as an instruction
+// (including instructions that run off the end of the program, or that set modes for parameters
+// they don't have) is listed as data. That way `source` always assembles back into the same program.
+use super::instruction::{Instruction, ParamMode};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Operand {
+    pub mode: ParamMode,
+    pub value: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Statement {
+    Instruction {
+        instruction: Instruction,
+        operands: Vec<Operand>,
+    },
+    Data(i64),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Line {
+    pub address: usize,
+    /// The values in the program the statement was decoded from.
+    pub raw: Vec<i64>,
+    pub statement: Statement,
+}
+
+/// Decodes a program into one line per instruction or data value.
+///
+/// # Examples
+///
+/// ```
+/// use advent_of_code::computer::disassembler::disassemble;
+/// let lines = disassemble(&[1002, 4, 3, 4, 33]);
+///
+/// assert_eq!(lines[0].statement.to_string(), "MUL [4], #3, [4]");
+/// assert_eq!(lines[1].statement.to_string(), "DATA 33");
+/// ```
+pub fn disassemble(program: &[i64]) -> Vec<Line> {
+    let mut lines = Vec::new();
+    let mut address = 0;
+    while address < program.len() {
+        let line = match decode_at(program, address) {
+            Some(line) => line,
+            None => Line {
+                address,
+                raw: vec![program[address]],
+                statement: Statement::Data(program[address]),
+            },
+        };
+        address += line.raw.len();
+        lines.push(line);
+    }
+    lines
+}
+
+/// Disassembles a program into assembly source, one statement per line, that the assembler
+/// turns back into the same program.
+pub fn source(program: &[i64]) -> String {
+    disassemble(program)
+        .iter()
+        .map(|line| format!("{}\n", line.statement))
+        .collect()
+}
+
+/// Disassembles a program into a listing with addresses and raw values alongside each line.
+pub fn listing(program: &[i64]) -> String {
+    let lines = disassemble(program);
+    let raw_width = lines
+        .iter()
+        .map(|line| raw_values(line).len())
+        .max()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|line| {
+            format!(
+                "{:>6}  {:<width$}  {}\n",
+                line.address,
+                raw_values(line),
+                line.statement,
+                width = raw_width
+            )
+        })
+        .collect()
+}
+
+fn decode_at(program: &[i64], address: usize) -> Option<Line> {
+    let instruction = Instruction::decode(program[address]).ok()?;
+    let param_count = instruction.opcode.param_count();
+    if instruction.modes[param_count..]
+        .iter()
+        .any(|mode| *mode != ParamMode::Position)
+    {
+        return None;
+    }
+    let raw = program.get(address..address + instruction.size())?.to_vec();
+    let operands = raw[1..]
+        .iter()
+        .zip(instruction.modes.iter())
+        .map(|(value, mode)| Operand {
+            mode: *mode,
+            value: *value,
+        })
+        .collect();
+    Some(Line {
+        address,
+        raw,
+        statement: Statement::Instruction {
+            instruction,
+            operands,
+        },
+    })
+}
+
+fn raw_values(line: &Line) -> String {
+    line.raw
+        .iter()
+        .map(|value| value.to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.mode {
+            ParamMode::Position => write!(f, "[{}]", self.value),
+            ParamMode::Immediate => write!(f, "#{}", self.value),
+            ParamMode::Relative if self.value < 0 => write!(f, "[rb{}]", self.value),
+            ParamMode::Relative => write!(f, "[rb+{}]", self.value),
+        }
+    }
+}
+
+impl fmt::Display for Statement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Statement::Instruction {
+                instruction,
+                operands,
+            } => {
+                write!(f, "{}", instruction.opcode.mnemonic())?;
+                for (i, operand) in operands.iter().enumerate() {
+                    let separator = if i == 0 { " " } else { ", " };
+                    write!(f, "{}{}", separator, operand)?;
+                }
+                Ok(())
+            }
+            Statement::Data(value) => write!(f, "DATA {}", value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_instructions_with_addresses_and_raw_values() {
+        let program = vec![109, -1, 1101, 5, 6, 7, 204, 2, 99];
+        assert_eq!(
+            listing(&program).lines().collect::<Vec<&str>>(),
+            vec![
+                "     0  109,-1      ARB #-1",
+                "     2  1101,5,6,7  ADD #5, #6, [7]",
+                "     6  204,2       OUT [rb+2]",
+                "     8  99          HLT",
+            ]
+        );
+    }
+
+    #[test]
+    fn lists_undecodable_values_as_data() {
+        let lines = disassemble(&[99, 42, 1, 0]);
+        let statements: Vec<String> = lines.iter().map(|l| l.statement.to_string()).collect();
+        assert_eq!(statements, vec!["HLT", "DATA 42", "DATA 1", "DATA 0"]);
+
+        let lines = disassemble(&[10099, 11104, 1]);
+        let statements: Vec<String> = lines.iter().map(|l| l.statement.to_string()).collect();
+        assert_eq!(statements, vec!["DATA 10099", "DATA 11104", "DATA 1"]);
+    }
+}
@@ -0,0 +1,189 @@
+use crate::error::IntcodeErrorKind;
+
+// An instruction is stored as a single integer: the rightmost two digits are the opcode and
+// each digit to the left of those is the mode of one parameter, read right to left.
+//   1002 => opcode 02, first param mode 0, second param mode 1, third param mode 0 (missing digits are 0)
+
+pub const ALL_OPCODES: [Opcode; 10] = [
+    Opcode::Add,
+    Opcode::Multiply,
+    Opcode::Input,
+    Opcode::Output,
+    Opcode::JumpIfTrue,
+    Opcode::JumpIfFalse,
+    Opcode::LessThan,
+    Opcode::Equals,
+    Opcode::AdjustRelativeBase,
+    Opcode::Halt,
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Opcode {
+    Add,
+    Multiply,
+    Input,
+    Output,
+    JumpIfTrue,
+    JumpIfFalse,
+    LessThan,
+    Equals,
+    AdjustRelativeBase,
+    Halt,
+}
+
+impl Opcode {
+    pub fn from_code(code: i64) -> Option<Opcode> {
+        match code {
+            1 => Some(Opcode::Add),
+            2 => Some(Opcode::Multiply),
+            3 => Some(Opcode::Input),
+            4 => Some(Opcode::Output),
+            5 => Some(Opcode::JumpIfTrue),
+            6 => Some(Opcode::JumpIfFalse),
+            7 => Some(Opcode::LessThan),
+            8 => Some(Opcode::Equals),
+            9 => Some(Opcode::AdjustRelativeBase),
+            99 => Some(Opcode::Halt),
+            _ => None,
+        }
+    }
+
+    pub fn code(self) -> i64 {
+        match self {
+            Opcode::Add => 1,
+            Opcode::Multiply => 2,
+            Opcode::Input => 3,
+            Opcode::Output => 4,
+            Opcode::JumpIfTrue => 5,
+            Opcode::JumpIfFalse => 6,
+            Opcode::LessThan => 7,
+            Opcode::Equals => 8,
+            Opcode::AdjustRelativeBase => 9,
+            Opcode::Halt => 99,
+        }
+    }
+
+    /// The short name used for the opcode in assembly listings.
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Opcode::Add => "ADD",
+            Opcode::Multiply => "MUL",
+            Opcode::Input => "IN",
+            Opcode::Output => "OUT",
+            Opcode::JumpIfTrue => "JNZ",
+            Opcode::JumpIfFalse => "JZ",
+            Opcode::LessThan => "LT",
+            Opcode::Equals => "EQ",
+            Opcode::AdjustRelativeBase => "ARB",
+            Opcode::Halt => "HLT",
+        }
+    }
+
+    pub fn from_mnemonic(mnemonic: &str) -> Option<Opcode> {
+        ALL_OPCODES
+            .iter()
+            .copied()
+            .find(|opcode| opcode.mnemonic().eq_ignore_ascii_case(mnemonic))
+    }
+
+    /// The index of the parameter the instruction writes its result to, if it has one.
+    pub fn written_param(self) -> Option<usize> {
+        match self {
+            Opcode::Add | Opcode::Multiply | Opcode::LessThan | Opcode::Equals => Some(2),
+            Opcode::Input => Some(0),
+            _ => None,
+        }
+    }
+
+    /// The number of parameters that follow the instruction in memory.
+    pub fn param_count(self) -> usize {
+        match self {
+            Opcode::Add | Opcode::Multiply | Opcode::LessThan | Opcode::Equals => 3,
+            Opcode::JumpIfTrue | Opcode::JumpIfFalse => 2,
+            Opcode::Input | Opcode::Output | Opcode::AdjustRelativeBase => 1,
+            Opcode::Halt => 0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ParamMode {
+    /// The parameter is the address of the value.
+    Position,
+    /// The parameter is the value itself.
+    Immediate,
+    /// The parameter is an offset from the relative base to the address of the value.
+    Relative,
+}
+
+impl ParamMode {
+    pub fn from_code(code: i64) -> Option<ParamMode> {
+        match code {
+            0 => Some(ParamMode::Position),
+            1 => Some(ParamMode::Immediate),
+            2 => Some(ParamMode::Relative),
+            _ => None,
+        }
+    }
+
+    pub fn code(self) -> i64 {
+        match self {
+            ParamMode::Position => 0,
+            ParamMode::Immediate => 1,
+            ParamMode::Relative => 2,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    pub modes: [ParamMode; 3],
+}
+
+impl Instruction {
+    /// Decodes the opcode and parameter modes packed into a single integer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use advent_of_code::computer::instruction::*;
+    /// let instruction = Instruction::decode(1002).unwrap();
+    ///
+    /// assert_eq!(instruction.opcode, Opcode::Multiply);
+    /// assert_eq!(
+    ///     instruction.modes,
+    ///     [ParamMode::Position, ParamMode::Immediate, ParamMode::Position]
+    /// );
+    /// ```
+    pub fn decode(value: i64) -> Result<Instruction, IntcodeErrorKind> {
+        let opcode =
+            Opcode::from_code(value % 100).ok_or(IntcodeErrorKind::UnknownOpcode(value % 100))?;
+        let mut modes = [ParamMode::Position; 3];
+        let mut remaining_modes = value / 100;
+        for mode in modes.iter_mut() {
+            *mode = ParamMode::from_code(remaining_modes % 10)
+                .ok_or(IntcodeErrorKind::InvalidParamMode(remaining_modes % 10))?;
+            remaining_modes /= 10;
+        }
+        if remaining_modes != 0 {
+            return Err(IntcodeErrorKind::InvalidParamMode(remaining_modes));
+        }
+        Ok(Instruction { opcode, modes })
+    }
+
+    /// Packs the instruction back into the integer it was decoded from.
+    pub fn encode(&self) -> i64 {
+        self.modes
+            .iter()
+            .rev()
+            .fold(0, |acc, mode| acc * 10 + mode.code())
+            * 100
+            + self.opcode.code()
+    }
+
+    /// The number of addresses the instruction takes up, including its parameters.
+    pub fn size(&self) -> usize {
+        self.opcode.param_count() + 1
+    }
+}
@@ -0,0 +1,85 @@
+// Where a Computer gets its inputs from (opcode 3) and sends its outputs to (opcode 4).
+// Anything queued with `push_input` is always read first, the input source is only asked
+// once that queue is empty. Every output is kept in `Computer::outputs` as well as being
+// written to the output sink.
+use std::collections::VecDeque;
+use std::io::{self, Write};
+
+pub trait Input: Send {
+    /// Returns the next input value, or `None` if there isn't one available.
+    fn read(&mut self) -> Option<i64>;
+    fn box_clone(&self) -> Box<dyn Input>;
+}
+
+pub trait Output: Send {
+    fn write(&mut self, value: i64);
+    fn box_clone(&self) -> Box<dyn Output>;
+}
+
+impl Clone for Box<dyn Input> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
+}
+
+impl Clone for Box<dyn Output> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
+}
+
+impl Input for VecDeque<i64> {
+    fn read(&mut self) -> Option<i64> {
+        self.pop_front()
+    }
+
+    fn box_clone(&self) -> Box<dyn Input> {
+        Box::new(self.clone())
+    }
+}
+
+impl Output for Vec<i64> {
+    fn write(&mut self, value: i64) {
+        self.push(value);
+    }
+
+    fn box_clone(&self) -> Box<dyn Output> {
+        Box::new(self.clone())
+    }
+}
+
+/// Prompts for inputs on stdin and prints outputs to stdout.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Terminal;
+
+// Keeps asking until it's given a number, stdin closing counts as there being no input
+impl Input for Terminal {
+    fn read(&mut self) -> Option<i64> {
+        println!("Please enter your input");
+        loop {
+            let mut input = String::new();
+            io::stdout().flush().ok()?;
+            if io::stdin().read_line(&mut input).ok()? == 0 {
+                return None;
+            }
+            match input.trim().parse::<i64>() {
+                Ok(value) => return Some(value),
+                Err(_) => println!("{} isn't a number, please try again", input.trim()),
+            }
+        }
+    }
+
+    fn box_clone(&self) -> Box<dyn Input> {
+        Box::new(*self)
+    }
+}
+
+impl Output for Terminal {
+    fn write(&mut self, value: i64) {
+        println!("{}", value);
+    }
+
+    fn box_clone(&self) -> Box<dyn Output> {
+        Box::new(*self)
+    }
+}
@@ -0,0 +1,375 @@
+// Runs a cluster of Intcode machines that talk to each other by sending packets.
+// Each machine is booted with its network address as its first input, then sends a packet by
+// outputting three values (destination address, x, y) and receives one as two inputs (x, y).
+// A machine that asks for an input when none has arrived is given -1.
+//
+// The network runs in rounds. In each round every machine takes a turn, running until it
+// asks for an input it doesn't have, then the packets sent during the round are handed to
+// the router to deliver. Turns can run one after another or on a thread per machine; routing
+// always happens in address order, so both schedules give the same result.
+//
+// The network is idle once every machine has asked for input at least twice with nothing to
+// read and no packets are in flight. The router then gets the chance to wake it back up.
+use super::{Computer, ComputerActions, Status};
+use crate::error::IntcodeError;
+use std::collections::VecDeque;
+use std::thread;
+
+// The number of empty reads in a row before a machine counts as idle
+const IDLE_READS: usize = 2;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Packet {
+    pub destination: i64,
+    pub x: i64,
+    pub y: i64,
+}
+
+/// What the router wants done with a packet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// Deliver the packet to the machine at its destination. Packets for addresses outside
+    /// the network are dropped.
+    Deliver(Packet),
+    Drop,
+    /// Stop the network.
+    Stop,
+}
+
+pub trait Router {
+    /// Called with every packet a machine sends.
+    fn route(&mut self, source: usize, packet: Packet) -> Action;
+
+    /// Called when the network goes idle. Return a packet to deliver to wake it back up, or
+    /// `None` to stop the network.
+    fn idle(&mut self) -> Option<Packet> {
+        None
+    }
+}
+
+/// Delivers every packet to its destination and stops once the network goes idle.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DirectRouter;
+
+impl Router for DirectRouter {
+    fn route(&mut self, _source: usize, packet: Packet) -> Action {
+        Action::Deliver(packet)
+    }
+}
+
+/// Monitors the network from its own address (255 by default). It keeps the last packet sent to
+/// it, and sends that packet to address 0 whenever the network goes idle. It stops the network
+/// once it's about to deliver the same y value twice in a row.
+#[derive(Clone, Debug)]
+pub struct Nat {
+    pub address: i64,
+    /// The first packet sent to the NAT.
+    pub first_packet: Option<Packet>,
+    /// The y value the NAT was about to deliver twice in a row when it stopped the network.
+    pub repeated_y: Option<i64>,
+    last_packet: Option<Packet>,
+    last_delivered_y: Option<i64>,
+}
+
+impl Nat {
+    pub fn new(address: i64) -> Self {
+        Nat {
+            address,
+            first_packet: None,
+            repeated_y: None,
+            last_packet: None,
+            last_delivered_y: None,
+        }
+    }
+}
+
+impl Default for Nat {
+    fn default() -> Self {
+        Nat::new(255)
+    }
+}
+
+impl Router for Nat {
+    fn route(&mut self, _source: usize, packet: Packet) -> Action {
+        if packet.destination != self.address {
+            return Action::Deliver(packet);
+        }
+        if self.first_packet.is_none() {
+            self.first_packet = Some(packet);
+        }
+        self.last_packet = Some(packet);
+        Action::Drop
+    }
+
+    fn idle(&mut self) -> Option<Packet> {
+        let packet = self.last_packet?;
+        if self.last_delivered_y == Some(packet.y) {
+            self.repeated_y = Some(packet.y);
+            return None;
+        }
+        self.last_delivered_y = Some(packet.y);
+        Some(Packet {
+            destination: 0,
+            ..packet
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Schedule {
+    /// Machines take their turns one after another on the current thread.
+    Cooperative,
+    /// Machines take their turns at the same time, each on its own thread.
+    Threaded,
+}
+
+struct Machine {
+    computer: Computer,
+    queue: VecDeque<i64>,
+    partial_packet: Vec<i64>,
+    empty_reads: usize,
+    halted: bool,
+}
+
+pub struct Network<R: Router> {
+    machines: Vec<Machine>,
+    router: R,
+    schedule: Schedule,
+}
+
+impl Network<DirectRouter> {
+    pub fn new(computer: &Computer, size: usize) -> Self {
+        Network::with_router(computer, size, DirectRouter)
+    }
+}
+
+impl<R: Router> Network<R> {
+    /// Boots `size` copies of the computer, giving each one its address.
+    pub fn with_router(computer: &Computer, size: usize, router: R) -> Self {
+        let machines = (0..size)
+            .map(|address| Machine {
+                computer: computer.clone(),
+                queue: vec![address as i64].into_iter().collect(),
+                partial_packet: Vec::new(),
+                empty_reads: 0,
+                halted: false,
+            })
+            .collect();
+        Network {
+            machines,
+            router,
+            schedule: Schedule::Cooperative,
+        }
+    }
+
+    pub fn set_schedule(&mut self, schedule: Schedule) {
+        self.schedule = schedule;
+    }
+
+    pub fn router(&self) -> &R {
+        &self.router
+    }
+
+    pub fn computer(&self, address: usize) -> Option<&Computer> {
+        self.machines.get(address).map(|machine| &machine.computer)
+    }
+
+    /// Queues a packet for its destination, returning false if there's no machine at that address.
+    pub fn send(&mut self, packet: Packet) -> bool {
+        if packet.destination < 0 {
+            return false;
+        }
+        match self.machines.get_mut(packet.destination as usize) {
+            Some(machine) => {
+                machine.queue.push_back(packet.x);
+                machine.queue.push_back(packet.y);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.machines.iter().all(|machine| {
+            machine.halted || (machine.queue.is_empty() && machine.empty_reads >= IDLE_READS)
+        })
+    }
+
+    /// Runs every machine for one turn and routes the packets they sent. Returns false if the
+    /// router asked for the network to stop.
+    pub fn round(&mut self) -> Result<bool, IntcodeError> {
+        let sent = match self.schedule {
+            Schedule::Cooperative => self
+                .machines
+                .iter_mut()
+                .map(take_turn)
+                .collect::<Vec<Result<Vec<Packet>, IntcodeError>>>(),
+            Schedule::Threaded => thread::scope(|scope| {
+                let turns: Vec<_> = self
+                    .machines
+                    .iter_mut()
+                    .map(|machine| scope.spawn(move || take_turn(machine)))
+                    .collect();
+                turns
+                    .into_iter()
+                    .map(|turn| turn.join().expect("Machine thread panicked"))
+                    .collect()
+            }),
+        };
+
+        for (source, packets) in sent.into_iter().enumerate() {
+            for packet in packets? {
+                match self.router.route(source, packet) {
+                    Action::Deliver(packet) => {
+                        self.send(packet);
+                    }
+                    Action::Drop => {}
+                    Action::Stop => return Ok(false),
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    /// Runs rounds until the router stops the network, or every machine has halted.
+    pub fn run(&mut self) -> Result<(), IntcodeError> {
+        while self.round()? {
+            if self.machines.iter().all(|machine| machine.halted) {
+                return Ok(());
+            }
+            if self.is_idle() {
+                match self.router.idle() {
+                    Some(packet) => {
+                        self.send(packet);
+                    }
+                    None => return Ok(()),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// Hands the machine everything that's arrived for it (or -1 if nothing has) and runs it
+// until it asks for more
+fn take_turn(machine: &mut Machine) -> Result<Vec<Packet>, IntcodeError> {
+    let mut packets = Vec::new();
+    if machine.halted {
+        return Ok(packets);
+    }
+    if machine.queue.is_empty() {
+        machine.computer.push_input(-1);
+        machine.empty_reads += 1;
+    } else {
+        for value in machine.queue.drain(..) {
+            machine.computer.push_input(value);
+        }
+        machine.empty_reads = 0;
+    }
+
+    loop {
+        match machine.computer.run_until() {
+            Status::Output(value) => {
+                machine.partial_packet.push(value);
+                if machine.partial_packet.len() == 3 {
+                    packets.push(Packet {
+                        destination: machine.partial_packet[0],
+                        x: machine.partial_packet[1],
+                        y: machine.partial_packet[2],
+                    });
+                    machine.partial_packet.clear();
+                    machine.empty_reads = 0;
+                }
+            }
+            Status::Halted => {
+                machine.halted = true;
+                return Ok(packets);
+            }
+            Status::Error(error) => return Err(error),
+            Status::NeedsInput | Status::Running => return Ok(packets),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::computer::assembler::assemble;
+
+    // Machine 0 starts a packet off round a ring of 4 machines. Each one adds 1 to x and passes
+    // it on to the next address, apart from machine 3 which sends it to 255.
+    const RING: &str = "
+                IN [addr]
+                JNZ [addr], #loop
+                OUT #1
+                OUT #0
+                OUT #7
+        loop:   IN [x]
+                EQ [x], #-1, [tmp]
+                JNZ [tmp], #loop
+                IN [y]
+                ADD [addr], #1, [dest]
+                EQ [addr], #3, [tmp]
+                JZ [tmp], #send
+                ADD #255, #0, [dest]
+        send:   OUT [dest]
+                ADD [x], #1, [x]
+                OUT [x]
+                OUT [y]
+                JZ #0, #loop
+        addr:   DATA 0
+        x:      DATA 0
+        y:      DATA 0
+        dest:   DATA 0
+        tmp:    DATA 0
+    ";
+
+    fn ring() -> Computer {
+        Computer::new(0, 0, assemble(RING).unwrap())
+    }
+
+    #[test]
+    fn nat_restarts_an_idle_network() {
+        for schedule in &[Schedule::Cooperative, Schedule::Threaded] {
+            let mut network = Network::with_router(&ring(), 4, Nat::default());
+            network.set_schedule(*schedule);
+            network.run().unwrap();
+            assert_eq!(
+                network.router().first_packet,
+                Some(Packet {
+                    destination: 255,
+                    x: 3,
+                    y: 7
+                })
+            );
+            assert_eq!(network.router().repeated_y, Some(7));
+        }
+    }
+
+    #[test]
+    fn custom_routers_can_stop_the_network() {
+        struct StopAtMachine(usize);
+        impl Router for StopAtMachine {
+            fn route(&mut self, source: usize, packet: Packet) -> Action {
+                if source == self.0 {
+                    Action::Stop
+                } else {
+                    Action::Deliver(packet)
+                }
+            }
+        }
+
+        let mut network = Network::with_router(&ring(), 4, StopAtMachine(2));
+        network.run().unwrap();
+        assert_eq!(network.computer(2).unwrap().outputs, vec![3, 2, 7]);
+        assert!(network.computer(3).unwrap().outputs.is_empty());
+    }
+
+    #[test]
+    fn direct_router_stops_once_idle() {
+        let mut network = Network::new(&ring(), 4);
+        network.run().unwrap();
+        assert!(network.is_idle());
+        assert_eq!(network.computer(3).unwrap().outputs, vec![255, 3, 7]);
+    }
+}
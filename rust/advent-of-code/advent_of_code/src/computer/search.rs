@@ -0,0 +1,127 @@
+// Searches for the nouns and verbs (the values at addresses 1 and 2) that make a program leave
+// a given value at address 0 when it halts. The nouns are shared out between threads, each of
+// which runs its own clone of the computer. Candidates that fault or wait for input are skipped.
+use super::{Computer, ComputerActions};
+use std::ops::Range;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+#[derive(Clone, Debug)]
+pub struct NounVerbSearch {
+    nouns: Range<i64>,
+    verbs: Range<i64>,
+    threads: usize,
+    stop_at_first: bool,
+}
+
+impl NounVerbSearch {
+    /// Searches every pair of noun and verb in the ranges, on as many threads as the machine
+    /// can run at once.
+    pub fn new(nouns: Range<i64>, verbs: Range<i64>) -> Self {
+        NounVerbSearch {
+            nouns,
+            verbs,
+            threads: thread::available_parallelism()
+                .map(|threads| threads.get())
+                .unwrap_or(1),
+            stop_at_first: false,
+        }
+    }
+
+    pub fn set_threads(&mut self, threads: usize) {
+        self.threads = threads.max(1);
+    }
+
+    /// Stops every thread as soon as one of them finds a match. The match returned is whichever
+    /// was found first, which isn't necessarily the smallest.
+    pub fn set_stop_at_first(&mut self, stop_at_first: bool) {
+        self.stop_at_first = stop_at_first;
+    }
+
+    /// Returns every (noun, verb) pair that leaves target at address 0, in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use advent_of_code::computer::search::NounVerbSearch;
+    /// use advent_of_code::computer::{Computer, ComputerActions};
+    /// // Adds the noun and verb together
+    /// let computer = Computer::new(0, 0, vec![1101, 0, 0, 0, 99]);
+    ///
+    /// let matches = NounVerbSearch::new(0..4, 0..4).run(&computer, 3);
+    /// assert_eq!(matches, vec![(0, 3), (1, 2), (2, 1), (3, 0)]);
+    /// ```
+    pub fn run(&self, computer: &Computer, target: i64) -> Vec<(i64, i64)> {
+        let found = AtomicBool::new(false);
+        let mut matches: Vec<(i64, i64)> = thread::scope(|scope| {
+            let searches: Vec<_> = (0..self.threads)
+                .map(|thread| {
+                    let mut computer = computer.clone();
+                    let found = &found;
+                    scope.spawn(move || {
+                        let mut matches = Vec::new();
+                        let nouns = self.nouns.clone().skip(thread).step_by(self.threads);
+                        for noun in nouns {
+                            for verb in self.verbs.clone() {
+                                if self.stop_at_first && found.load(Ordering::Relaxed) {
+                                    return matches;
+                                }
+                                computer.reset(noun, verb);
+                                if computer.run_to_halt().is_ok()
+                                    && computer.read_memory(0) == target
+                                {
+                                    matches.push((noun, verb));
+                                    found.store(true, Ordering::Relaxed);
+                                }
+                            }
+                        }
+                        matches
+                    })
+                })
+                .collect();
+            searches
+                .into_iter()
+                .flat_map(|search| search.join().expect("Search thread panicked"))
+                .collect()
+        });
+        matches.sort_unstable();
+        if self.stop_at_first {
+            matches.truncate(1);
+        }
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Multiplies the noun and verb together
+    const MULTIPLY: [i64; 5] = [1102, 0, 0, 0, 99];
+
+    #[test]
+    fn finds_every_match() {
+        let computer = Computer::new(0, 0, MULTIPLY.to_vec());
+        for threads in 1..4 {
+            let mut search = NounVerbSearch::new(0..10, 0..10);
+            search.set_threads(threads);
+            assert_eq!(
+                search.run(&computer, 12),
+                vec![(2, 6), (3, 4), (4, 3), (6, 2)]
+            );
+        }
+        assert!(NounVerbSearch::new(0..10, 0..10)
+            .run(&computer, 97)
+            .is_empty());
+    }
+
+    #[test]
+    fn stops_at_the_first_match() {
+        let computer = Computer::new(0, 0, MULTIPLY.to_vec());
+        let mut search = NounVerbSearch::new(0..10, 0..10);
+        search.set_stop_at_first(true);
+        let matches = search.run(&computer, 12);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].0 * matches[0].1, 12);
+    }
+}
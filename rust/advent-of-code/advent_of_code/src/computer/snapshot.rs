@@ -0,0 +1,78 @@
+// Everything needed to pick a program back up from where it was: memory, the instruction
+// pointer and relative base, and any inputs and outputs that were waiting. Snapshots are saved
+// as JSON. The input source, output sink and tracer aren't part of a snapshot, so restoring one
+// keeps whatever the computer already has.
+use crate::error::Error;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub noun: i64,
+    pub verb: i64,
+    pub instruction_pointer: usize,
+    pub relative_base: i64,
+    /// The program as it was loaded, which `reset` goes back to.
+    pub start_input: Vec<i64>,
+    pub memory: Vec<i64>,
+    /// Inputs that were pushed but haven't been read yet.
+    pub inputs: Vec<i64>,
+    pub outputs: Vec<i64>,
+}
+
+impl Snapshot {
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::computer::{Computer, ComputerActions, Status};
+    use std::env;
+
+    // Adds its two inputs together, outputs the total and halts
+    const ADDER: [i64; 13] = [3, 11, 3, 12, 1, 11, 12, 11, 4, 11, 99, 0, 0];
+
+    #[test]
+    fn resumes_from_a_saved_snapshot() {
+        let mut computer = Computer::new(0, 0, ADDER.to_vec());
+        computer.push_input(2);
+        assert_eq!(computer.run(), Status::NeedsInput);
+
+        let path = env::temp_dir().join(format!("intcode-snapshot-{}.json", std::process::id()));
+        computer.snapshot().save(&path).unwrap();
+        let snapshot = Snapshot::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(snapshot, computer.snapshot());
+
+        for _ in 0..2 {
+            let mut restored = Computer::new(0, 0, Vec::new());
+            restored.restore(&snapshot);
+            restored.push_input(3);
+            assert_eq!(restored.run(), Status::Halted);
+            assert_eq!(restored.outputs, vec![5]);
+        }
+    }
+
+    #[test]
+    fn keeps_pending_inputs() {
+        let mut computer = Computer::new(0, 0, ADDER.to_vec());
+        computer.push_input(4);
+        computer.push_input(6);
+        let snapshot = computer.snapshot();
+        assert_eq!(snapshot.inputs, vec![4, 6]);
+
+        let mut restored = Computer::new(0, 0, Vec::new());
+        restored.restore(&snapshot);
+        assert_eq!(restored.run(), Status::Halted);
+        assert_eq!(restored.outputs, vec![10]);
+    }
+}
@@ -0,0 +1,571 @@
+// Runs a program with named symbols in place of some of its memory (e.g. the noun and verb) and
+// of its inputs, building up an expression for every value it computes instead of a number.
+// When the expression for a result is affine (a constant plus a multiple of each symbol), the
+// values of the symbols that give a target result can be worked out directly, without running
+// the program for every candidate.
+//
+// The analysis follows a single path through the program, so it gives up if a jump, or the
+// address of a write, depends on a symbol. Reading from an address that depends on a symbol
+// gives an unknown value, which is fine as long as it's overwritten before it's used.
+use super::instruction::{Instruction, Opcode, ParamMode};
+use super::search::NounVerbSearch;
+use super::{checked, Computer, ComputerActions, MEMORY_LIMIT};
+use crate::error::{Error, IntcodeError, IntcodeErrorKind};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Range;
+
+// Gives up on programs that look like they'll never halt
+const MAX_STEPS: usize = 1_000_000;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr {
+    Constant(i64),
+    Symbol(String),
+    Add(Box<Expr>, Box<Expr>),
+    Multiply(Box<Expr>, Box<Expr>),
+    LessThan(Box<Expr>, Box<Expr>),
+    Equals(Box<Expr>, Box<Expr>),
+    /// A value read from an address that depends on a symbol.
+    Unknown,
+}
+
+impl Expr {
+    // The constructors work out anything that only involves constants straight away, which
+    // is a fault if it overflows, as it would be running the program
+    fn add(left: Expr, right: Expr) -> Result<Expr, IntcodeErrorKind> {
+        Ok(match (left, right) {
+            (Expr::Constant(a), Expr::Constant(b)) => Expr::Constant(checked(a.checked_add(b))?),
+            (Expr::Constant(0), other) | (other, Expr::Constant(0)) => other,
+            (left, right) => Expr::Add(Box::new(left), Box::new(right)),
+        })
+    }
+
+    fn multiply(left: Expr, right: Expr) -> Result<Expr, IntcodeErrorKind> {
+        Ok(match (left, right) {
+            (Expr::Constant(a), Expr::Constant(b)) => Expr::Constant(checked(a.checked_mul(b))?),
+            (Expr::Constant(0), _) | (_, Expr::Constant(0)) => Expr::Constant(0),
+            (Expr::Constant(1), other) | (other, Expr::Constant(1)) => other,
+            (left, right) => Expr::Multiply(Box::new(left), Box::new(right)),
+        })
+    }
+
+    fn less_than(left: Expr, right: Expr) -> Expr {
+        match (left, right) {
+            (Expr::Constant(a), Expr::Constant(b)) => Expr::Constant((a < b) as i64),
+            (left, right) => Expr::LessThan(Box::new(left), Box::new(right)),
+        }
+    }
+
+    fn equals(left: Expr, right: Expr) -> Expr {
+        match (left, right) {
+            (Expr::Constant(a), Expr::Constant(b)) => Expr::Constant((a == b) as i64),
+            (left, right) => Expr::Equals(Box::new(left), Box::new(right)),
+        }
+    }
+
+    pub fn constant(&self) -> Option<i64> {
+        match self {
+            Expr::Constant(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// The expression as a constant plus a multiple of each symbol, if it can be written that way.
+    /// Fails if the constant or a coefficient doesn't fit in an i64.
+    pub fn affine(&self) -> Result<Option<Affine>, IntcodeErrorKind> {
+        Ok(match self {
+            Expr::Constant(value) => Some(Affine {
+                constant: *value,
+                coefficients: BTreeMap::new(),
+            }),
+            Expr::Symbol(name) => Some(Affine {
+                constant: 0,
+                coefficients: vec![(name.clone(), 1)].into_iter().collect(),
+            }),
+            Expr::Add(left, right) => match (left.affine()?, right.affine()?) {
+                (Some(left), Some(right)) => Some(left.add(&right)?),
+                _ => None,
+            },
+            Expr::Multiply(left, right) => match (left.affine()?, right.affine()?) {
+                (Some(left), Some(right)) if left.coefficients.is_empty() => {
+                    Some(right.scale(left.constant)?)
+                }
+                (Some(left), Some(right)) if right.coefficients.is_empty() => {
+                    Some(left.scale(right.constant)?)
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+    }
+}
+
+/// `constant + coefficient * symbol + ...`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Affine {
+    pub constant: i64,
+    /// Symbols with a coefficient of 0 are left out.
+    pub coefficients: BTreeMap<String, i64>,
+}
+
+impl Affine {
+    fn add(&self, other: &Affine) -> Result<Affine, IntcodeErrorKind> {
+        let mut coefficients = self.coefficients.clone();
+        for (name, coefficient) in &other.coefficients {
+            let sum = coefficients.entry(name.clone()).or_insert(0);
+            *sum = checked(sum.checked_add(*coefficient))?;
+        }
+        coefficients.retain(|_, coefficient| *coefficient != 0);
+        Ok(Affine {
+            constant: checked(self.constant.checked_add(other.constant))?,
+            coefficients,
+        })
+    }
+
+    fn scale(&self, factor: i64) -> Result<Affine, IntcodeErrorKind> {
+        let coefficients = match factor {
+            0 => BTreeMap::new(),
+            _ => self
+                .coefficients
+                .iter()
+                .map(|(name, coefficient)| {
+                    Ok((name.clone(), checked(coefficient.checked_mul(factor))?))
+                })
+                .collect::<Result<_, IntcodeErrorKind>>()?,
+        };
+        Ok(Affine {
+            constant: checked(self.constant.checked_mul(factor))?,
+            coefficients,
+        })
+    }
+
+    /// Finds every assignment of values to symbols, each taken from its range, that makes the
+    /// expression equal target. Symbols without a range can't be solved for, so give nothing.
+    /// Every symbol but the last is tried in turn, and the last is solved for directly. Fails if
+    /// working out what's left to make up overflows.
+    pub fn solve(
+        &self,
+        target: i64,
+        ranges: &[(&str, Range<i64>)],
+    ) -> Result<Vec<Vec<i64>>, IntcodeErrorKind> {
+        if self
+            .coefficients
+            .keys()
+            .any(|name| !ranges.iter().any(|(symbol, _)| symbol == name))
+        {
+            return Ok(Vec::new());
+        }
+        let mut solutions = Vec::new();
+        self.solve_from(
+            checked(target.checked_sub(self.constant))?,
+            ranges,
+            &mut Vec::new(),
+            &mut solutions,
+        )?;
+        Ok(solutions)
+    }
+
+    // Picks values for the remaining symbols, with `remaining` left to make up
+    fn solve_from(
+        &self,
+        remaining: i64,
+        ranges: &[(&str, Range<i64>)],
+        values: &mut Vec<i64>,
+        solutions: &mut Vec<Vec<i64>>,
+    ) -> Result<(), IntcodeErrorKind> {
+        let ((name, range), rest) = match ranges.split_first() {
+            Some(first) => first,
+            None => {
+                if remaining == 0 {
+                    solutions.push(values.clone());
+                }
+                return Ok(());
+            }
+        };
+        let coefficient = self.coefficients.get(*name).copied().unwrap_or(0);
+        // The last symbol with a coefficient can be solved for rather than searched
+        let is_last = rest
+            .iter()
+            .all(|(name, _)| !self.coefficients.contains_key(*name));
+        let candidates: Vec<i64> = if is_last && coefficient != 0 {
+            let value = checked(remaining.checked_div(coefficient))?;
+            if checked(remaining.checked_rem(coefficient))? == 0 && range.contains(&value) {
+                vec![value]
+            } else {
+                Vec::new()
+            }
+        } else {
+            range.clone().collect()
+        };
+        for value in candidates {
+            values.push(value);
+            let remaining = checked(
+                coefficient
+                    .checked_mul(value)
+                    .and_then(|part| remaining.checked_sub(part)),
+            )?;
+            self.solve_from(remaining, rest, values, solutions)?;
+            values.pop();
+        }
+        Ok(())
+    }
+}
+
+/// What the program computed, in terms of its symbols.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Analysis {
+    /// Memory when the program halted.
+    pub memory: Vec<Expr>,
+    pub outputs: Vec<Expr>,
+    /// The number of inputs the program read. Input n is the symbol `input{n}`.
+    pub inputs: usize,
+}
+
+/// Runs the computer's program from the start, as it was loaded, with the named symbols in place
+/// of the values at the given addresses and a new symbol for every input it reads.
+pub fn analyse(computer: &Computer, symbols: &[(usize, &str)]) -> Result<Analysis, Error> {
+    let mut memory: Vec<Expr> = computer
+        .start_input()
+        .iter()
+        .map(|value| Expr::Constant(*value))
+        .collect();
+    for (address, name) in symbols {
+        if *address >= memory.len() {
+            memory.resize(address + 1, Expr::Constant(0));
+        }
+        memory[*address] = Expr::Symbol(name.to_string());
+    }
+    let mut machine = Machine {
+        memory,
+        instruction_pointer: 0,
+        relative_base: 0,
+        outputs: Vec::new(),
+        inputs: 0,
+    };
+    for _ in 0..MAX_STEPS {
+        if !machine.step()? {
+            return Ok(Analysis {
+                memory: machine.memory,
+                outputs: machine.outputs,
+                inputs: machine.inputs,
+            });
+        }
+    }
+    Err(Error::Custom(format!(
+        "Gave up analysing the program after {} instructions",
+        MAX_STEPS
+    )))
+}
+
+/// Finds every noun and verb in the ranges that leave target at address 0. If that value is
+/// affine in the noun and verb it's solved for directly, otherwise every pair is tried. So is
+/// any program the analysis fails on, e.g. because it overflows or writes past the memory limit,
+/// which leaves running it to decide which pairs fault.
+pub fn solve_noun_verb(
+    computer: &Computer,
+    nouns: Range<i64>,
+    verbs: Range<i64>,
+    target: i64,
+) -> Vec<(i64, i64)> {
+    let solutions = analyse(computer, &[(1, "noun"), (2, "verb")])
+        .ok()
+        .and_then(|analysis| analysis.memory.first()?.affine().ok()?)
+        .and_then(|result| {
+            result
+                .solve(target, &[("noun", nouns.clone()), ("verb", verbs.clone())])
+                .ok()
+        });
+    match solutions {
+        Some(solutions) => solutions
+            .into_iter()
+            .map(|values| (values[0], values[1]))
+            .collect(),
+        None => NounVerbSearch::new(nouns, verbs).run(computer, target),
+    }
+}
+
+struct Machine {
+    memory: Vec<Expr>,
+    instruction_pointer: usize,
+    relative_base: i64,
+    outputs: Vec<Expr>,
+    inputs: usize,
+}
+
+impl Machine {
+    fn read_memory(&self, address: usize) -> Expr {
+        self.memory
+            .get(address)
+            .cloned()
+            .unwrap_or(Expr::Constant(0))
+    }
+
+    // Works out the address a parameter refers to, or None if it depends on a symbol
+    fn param_address(
+        &self,
+        instruction: &Instruction,
+        param: usize,
+    ) -> Result<Option<usize>, Error> {
+        let value = match self
+            .read_memory(self.instruction_pointer + param + 1)
+            .constant()
+        {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+        let address = match instruction.modes[param] {
+            ParamMode::Position => value,
+            ParamMode::Relative => {
+                checked(self.relative_base.checked_add(value)).map_err(|kind| self.fault(kind))?
+            }
+            ParamMode::Immediate => return Ok(None),
+        };
+        if address < 0 {
+            return Err(self.fault(IntcodeErrorKind::NegativeAddress(address)));
+        }
+        Ok(Some(address as usize))
+    }
+
+    fn read_param(&self, instruction: &Instruction, param: usize) -> Result<Expr, Error> {
+        if instruction.modes[param] == ParamMode::Immediate {
+            return Ok(self.read_memory(self.instruction_pointer + param + 1));
+        }
+        Ok(match self.param_address(instruction, param)? {
+            Some(address) => self.read_memory(address),
+            None => Expr::Unknown,
+        })
+    }
+
+    fn write_param(
+        &mut self,
+        instruction: &Instruction,
+        param: usize,
+        value: Expr,
+    ) -> Result<(), Error> {
+        if instruction.modes[param] == ParamMode::Immediate {
+            return Err(self.fault(IntcodeErrorKind::ImmediateModeWrite));
+        }
+        let address = self
+            .param_address(instruction, param)?
+            .ok_or_else(|| self.unsupported("writes to an address that depends on a symbol"))?;
+        if address >= MEMORY_LIMIT {
+            return Err(self.fault(IntcodeErrorKind::MemoryLimit(address)));
+        }
+        if address >= self.memory.len() {
+            self.memory.resize(address + 1, Expr::Constant(0));
+        }
+        self.memory[address] = value;
+        Ok(())
+    }
+
+    // Anything that decides where the program goes next has to be a constant
+    fn constant(&self, value: Expr, what: &str) -> Result<i64, Error> {
+        value
+            .constant()
+            .ok_or_else(|| self.unsupported(&format!("{} depends on a symbol", what)))
+    }
+
+    // Runs one instruction, returning false once the program halts
+    fn step(&mut self) -> Result<bool, Error> {
+        let value = self.read_memory(self.instruction_pointer);
+        let value = self.constant(value, "the instruction")?;
+        let instruction = Instruction::decode(value).map_err(|kind| self.fault(kind))?;
+        let mut next_instruction = self.instruction_pointer + instruction.size();
+
+        match instruction.opcode {
+            Opcode::Halt => return Ok(false),
+            Opcode::Add | Opcode::Multiply | Opcode::LessThan | Opcode::Equals => {
+                let left = self.read_param(&instruction, 0)?;
+                let right = self.read_param(&instruction, 1)?;
+                let value = match instruction.opcode {
+                    Opcode::Add => Expr::add(left, right),
+                    Opcode::Multiply => Expr::multiply(left, right),
+                    Opcode::LessThan => Ok(Expr::less_than(left, right)),
+                    _ => Ok(Expr::equals(left, right)),
+                }
+                .map_err(|kind| self.fault(kind))?;
+                self.write_param(&instruction, 2, value)?;
+            }
+            Opcode::Input => {
+                let symbol = Expr::Symbol(format!("input{}", self.inputs));
+                self.inputs += 1;
+                self.write_param(&instruction, 0, symbol)?;
+            }
+            Opcode::Output => {
+                let value = self.read_param(&instruction, 0)?;
+                self.outputs.push(value);
+            }
+            Opcode::JumpIfTrue | Opcode::JumpIfFalse => {
+                let condition = self.read_param(&instruction, 0)?;
+                let condition = self.constant(condition, "the jump")?;
+                if (condition != 0) == (instruction.opcode == Opcode::JumpIfTrue) {
+                    let target = self.read_param(&instruction, 1)?;
+                    let target = self.constant(target, "the jump target")?;
+                    if target < 0 {
+                        return Err(self.fault(IntcodeErrorKind::NegativeAddress(target)));
+                    }
+                    next_instruction = target as usize;
+                }
+            }
+            Opcode::AdjustRelativeBase => {
+                let offset = self.read_param(&instruction, 0)?;
+                let offset = self.constant(offset, "the relative base")?;
+                self.relative_base = checked(self.relative_base.checked_add(offset))
+                    .map_err(|kind| self.fault(kind))?;
+            }
+        }
+        self.instruction_pointer = next_instruction;
+        Ok(true)
+    }
+
+    fn fault(&self, kind: IntcodeErrorKind) -> Error {
+        Error::Intcode(IntcodeError {
+            kind,
+            instruction_pointer: self.instruction_pointer,
+            instruction: self
+                .read_memory(self.instruction_pointer)
+                .constant()
+                .unwrap_or(0),
+        })
+    }
+
+    fn unsupported(&self, reason: &str) -> Error {
+        Error::Custom(format!(
+            "Can't analyse the instruction at address {}: {}",
+            self.instruction_pointer, reason
+        ))
+    }
+}
+
+// e.g. (noun * 3) + 1
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expr::Constant(value) => write!(f, "{}", value),
+            Expr::Symbol(name) => write!(f, "{}", name),
+            Expr::Add(left, right) => write!(f, "({} + {})", left, right),
+            Expr::Multiply(left, right) => write!(f, "({} * {})", left, right),
+            Expr::LessThan(left, right) => write!(f, "({} < {})", left, right),
+            Expr::Equals(left, right) => write!(f, "({} == {})", left, right),
+            Expr::Unknown => write!(f, "?"),
+        }
+    }
+}
+
+// e.g. 270000 * noun + verb + 520625
+impl fmt::Display for Affine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut terms: Vec<String> = self
+            .coefficients
+            .iter()
+            .map(|(name, coefficient)| match coefficient {
+                1 => name.clone(),
+                _ => format!("{} * {}", coefficient, name),
+            })
+            .collect();
+        if self.constant != 0 || terms.is_empty() {
+            terms.push(self.constant.to_string());
+        }
+        write!(f, "{}", terms.join(" + "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::computer::ComputerActions;
+    use std::fs;
+
+    #[test]
+    fn solves_day_two_without_searching() {
+        let program = fs::read_to_string("./inputs/2019/day_two/input.txt").unwrap();
+        let computer = Computer::new(0, 0, crate::computer::parse_program(&program).unwrap());
+        let analysis = analyse(&computer, &[(1, "noun"), (2, "verb")]).unwrap();
+        let result = analysis.memory[0].affine().unwrap().unwrap();
+        assert_eq!(result.to_string(), "270000 * noun + verb + 520625");
+        assert_eq!(
+            solve_noun_verb(&computer, 0..100, 0..100, 19_690_720),
+            vec![(71, 95)]
+        );
+    }
+
+    #[test]
+    fn builds_expressions_for_outputs() {
+        // Outputs 7 + input0 * 2, then whether input1 is less than 5
+        let program = vec![
+            3, 21, 1002, 21, 2, 21, 101, 7, 21, 21, 4, 21, 3, 22, 1007, 22, 5, 23, 4, 23, 99,
+        ];
+        let computer = Computer::new(0, 0, program);
+        let analysis = analyse(&computer, &[]).unwrap();
+        assert_eq!(analysis.inputs, 2);
+        assert_eq!(analysis.outputs[0].to_string(), "(7 + (input0 * 2))");
+        assert_eq!(
+            analysis.outputs[0]
+                .affine()
+                .unwrap()
+                .unwrap()
+                .solve(15, &[("input0", 0..10)]),
+            Ok(vec![vec![4]])
+        );
+        assert_eq!(analysis.outputs[1].to_string(), "(input1 < 5)");
+        assert_eq!(analysis.outputs[1].affine(), Ok(None));
+    }
+
+    #[test]
+    fn gives_up_on_jumps_that_depend_on_symbols() {
+        // Jumps to the halt at address 7 unless the noun is 0
+        let computer = Computer::new(0, 0, vec![1105, 0, 7, 1101, 1, 1, 0, 99]);
+        let error = analyse(&computer, &[(1, "noun")]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Can't analyse the instruction at address 0: the jump depends on a symbol"
+        );
+    }
+
+    #[test]
+    fn searches_when_the_result_isnt_affine() {
+        // Multiplies the noun and verb together
+        let computer = Computer::new(0, 0, vec![1102, 0, 0, 0, 99]);
+        let analysis = analyse(&computer, &[(1, "noun"), (2, "verb")]).unwrap();
+        assert_eq!(analysis.memory[0].affine(), Ok(None));
+        assert_eq!(
+            solve_noun_verb(&computer, 0..10, 0..10, 12),
+            vec![(2, 6), (3, 4), (4, 3), (6, 2)]
+        );
+    }
+
+    #[test]
+    fn reports_overflow_and_writes_past_the_memory_limit() {
+        let fault = |program: Vec<i64>| match analyse(&Computer::new(0, 0, program), &[]) {
+            Err(Error::Intcode(error)) => Some(error.kind),
+            _ => None,
+        };
+        let overflowing_add = vec![1101, 0, 0, 0, 1101, i64::MAX, 1, 7, 99];
+        assert_eq!(fault(overflowing_add), Some(IntcodeErrorKind::Overflow));
+        let overflowing_base = vec![1101, 0, 0, 0, 109, i64::MAX, 109, 9, 99];
+        assert_eq!(fault(overflowing_base), Some(IntcodeErrorKind::Overflow));
+        assert_eq!(
+            fault(vec![1101, 1, 1, 999_999_999_999, 99]),
+            Some(IntcodeErrorKind::MemoryLimit(999_999_999_999))
+        );
+
+        // noun * i64::MAX * 2
+        let noun = Expr::Symbol(String::from("noun"));
+        let product = Expr::Multiply(
+            Box::new(Expr::Multiply(
+                Box::new(noun),
+                Box::new(Expr::Constant(i64::MAX)),
+            )),
+            Box::new(Expr::Constant(2)),
+        );
+        assert_eq!(product.affine(), Err(IntcodeErrorKind::Overflow));
+    }
+
+    #[test]
+    fn searches_when_the_analysis_fails() {
+        // Writes the noun plus the verb past the memory limit, so every pair faults
+        let computer = Computer::new(0, 0, vec![1101, 1, 1, 999_999_999_999, 99, 0, 0, 0]);
+        assert_eq!(solve_noun_verb(&computer, 0..10, 0..10, 2), vec![]);
+    }
+}
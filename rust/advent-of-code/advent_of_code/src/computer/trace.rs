@@ -0,0 +1,78 @@
+// A tracer is handed an entry for every instruction the Computer executes. Instructions that
+// don't run (an input instruction with nothing to read, or a fault) aren't traced.
+use super::instruction::Instruction;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TraceEntry {
+    pub instruction_pointer: usize,
+    pub instruction: Instruction,
+    /// The values of the parameters the instruction read, after resolving their modes.
+    pub operands: Vec<i64>,
+    /// The address and value the instruction wrote to memory, if it wrote anything.
+    pub write: Option<(usize, i64)>,
+}
+
+pub trait Tracer: Send {
+    fn record(&mut self, entry: &TraceEntry);
+    fn box_clone(&self) -> Box<dyn Tracer>;
+}
+
+impl Clone for Box<dyn Tracer> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
+}
+
+/// A trace that can be read while the computer is still running. Clones share the same entries,
+/// so keep a clone around to read what the computer's copy records.
+#[derive(Clone, Debug, Default)]
+pub struct TraceLog {
+    entries: Arc<Mutex<Vec<TraceEntry>>>,
+}
+
+impl TraceLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn entries(&self) -> Vec<TraceEntry> {
+        self.entries.lock().unwrap().clone()
+    }
+
+    /// Removes and returns everything recorded so far.
+    pub fn take(&self) -> Vec<TraceEntry> {
+        self.entries.lock().unwrap().drain(..).collect()
+    }
+}
+
+impl Tracer for TraceLog {
+    fn record(&mut self, entry: &TraceEntry) {
+        self.entries.lock().unwrap().push(entry.clone());
+    }
+
+    fn box_clone(&self) -> Box<dyn Tracer> {
+        Box::new(self.clone())
+    }
+}
+
+// e.g.     12  ADD 3, 4 -> [7] = 7
+impl fmt::Display for TraceEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:>6}  {}",
+            self.instruction_pointer,
+            self.instruction.opcode.mnemonic()
+        )?;
+        let operands: Vec<String> = self.operands.iter().map(|v| v.to_string()).collect();
+        if !operands.is_empty() {
+            write!(f, " {}", operands.join(", "))?;
+        }
+        if let Some((address, value)) = self.write {
+            write!(f, " -> [{}] = {}", address, value)?;
+        }
+        Ok(())
+    }
+}
@@ -5,6 +5,42 @@ use std::io;
 pub enum Error {
     Custom(String),
     Io(io::Error),
+    Json(serde_json::Error),
+    Toml(toml::de::Error),
+    Intcode(IntcodeError),
+    Parse(ParseError),
+}
+
+/// A fault raised by the Intcode computer, along with where it happened.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IntcodeError {
+    pub kind: IntcodeErrorKind,
+    pub instruction_pointer: usize,
+    /// The raw value at the instruction pointer when the fault happened.
+    pub instruction: i64,
+}
+
+/// Input that couldn't be parsed, and where in the input it is.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum IntcodeErrorKind {
+    UnknownOpcode(i64),
+    InvalidParamMode(i64),
+    /// A parameter that is written to was given in immediate mode.
+    ImmediateModeWrite,
+    NegativeAddress(i64),
+    /// The program needed an input but there was none left to give it.
+    MissingInput,
+    /// An addition, multiplication or relative base adjustment didn't fit in 64 bits.
+    Overflow,
+    /// A write to an address past the memory limit.
+    MemoryLimit(usize),
 }
 
 impl From<io::Error> for Error {
@@ -13,13 +49,85 @@ impl From<io::Error> for Error {
     }
 }
 
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Json(e)
+    }
+}
+
+impl From<toml::de::Error> for Error {
+    fn from(e: toml::de::Error) -> Self {
+        Self::Toml(e)
+    }
+}
+
+impl From<IntcodeError> for Error {
+    fn from(e: IntcodeError) -> Self {
+        Self::Intcode(e)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Self::Parse(e)
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match &self {
             Error::Custom(s) => write!(f, "{}", s),
             Error::Io(e) => write!(f, "{}", e),
+            Error::Json(e) => write!(f, "{}", e),
+            Error::Toml(e) => write!(f, "{}", e),
+            Error::Intcode(e) => write!(f, "{}", e),
+            Error::Parse(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl fmt::Display for IntcodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} at address {} (instruction {})",
+            self.kind, self.instruction_pointer, self.instruction
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {}",
+            self.message, self.line, self.column
+        )
+    }
+}
+
+impl fmt::Display for IntcodeErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self {
+            IntcodeErrorKind::UnknownOpcode(opcode) => write!(f, "Unknown opcode {}", opcode),
+            IntcodeErrorKind::InvalidParamMode(mode) => {
+                write!(f, "Invalid parameter mode {}", mode)
+            }
+            IntcodeErrorKind::ImmediateModeWrite => write!(f, "Write in immediate mode"),
+            IntcodeErrorKind::NegativeAddress(address) => {
+                write!(f, "Negative address {}", address)
+            }
+            IntcodeErrorKind::MissingInput => write!(f, "Missing input"),
+            IntcodeErrorKind::Overflow => write!(f, "Arithmetic overflow"),
+            IntcodeErrorKind::MemoryLimit(address) => {
+                write!(f, "Write to address {} past the memory limit", address)
+            }
         }
     }
 }
 
 impl std::error::Error for Error {}
+
+impl std::error::Error for IntcodeError {}
+
+impl std::error::Error for ParseError {}
@@ -0,0 +1,145 @@
+// Downloads puzzle inputs from the Advent of Code site into the inputs directory. Inputs are only
+// downloaded once: an input that's already in the inputs directory is never fetched again.
+// Requests are spaced out by at least a minimum interval, remembered between runs in a file in
+// the inputs directory, so fetching a whole year doesn't hammer the site.
+use crate::error::Error;
+use crate::input::default_path;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// The environment variable holding the session token.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// The shortest time to leave between requests.
+pub const MIN_INTERVAL: Duration = Duration::from_secs(5);
+
+// When the last request was made, in milliseconds since the Unix epoch
+const LAST_REQUEST_FILE: &str = ".last_fetch";
+
+const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " (",
+    env!("CARGO_PKG_AUTHORS"),
+    ")"
+);
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Fetched {
+    /// The input was already in the inputs directory.
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+pub struct Fetcher {
+    session: String,
+    inputs_dir: PathBuf,
+    base_url: String,
+    min_interval: Duration,
+}
+
+impl Fetcher {
+    pub fn new(session: String, inputs_dir: &Path) -> Self {
+        Fetcher {
+            session,
+            inputs_dir: inputs_dir.to_path_buf(),
+            base_url: String::from(BASE_URL),
+            min_interval: MIN_INTERVAL,
+        }
+    }
+
+    pub fn set_base_url(&mut self, base_url: &str) {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+    }
+
+    pub fn set_min_interval(&mut self, min_interval: Duration) {
+        self.min_interval = min_interval;
+    }
+
+    pub fn fetch(&self, year: u32, day: usize) -> Result<Fetched, Error> {
+        let path = default_path(&self.inputs_dir, year, day)?;
+        if path.exists() {
+            return Ok(Fetched::Cached(path));
+        }
+
+        self.wait_for_interval()?;
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        let response = ureq::get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+            .call();
+        self.record_request()?;
+        let input = match response {
+            Ok(response) => response.into_string()?,
+            Err(ureq::Error::Status(status, _)) => {
+                return Err(Error::Custom(match status {
+                    400 | 401 | 403 => format!("{} rejected the session token", self.base_url),
+                    404 => format!("Day {} of {} isn't available yet", day, year),
+                    429 => format!("{} is rate limiting requests, try later", self.base_url),
+                    _ => format!("Fetching {} failed with status {}", url, status),
+                }))
+            }
+            Err(e) => return Err(Error::Custom(format!("Couldn't fetch {}: {}", url, e))),
+        };
+
+        // Written to one side first so an interrupted download isn't taken for the input
+        fs::create_dir_all(path.parent().unwrap())?;
+        let partial = path.with_extension("partial");
+        fs::write(&partial, input)?;
+        fs::rename(&partial, &path)?;
+        Ok(Fetched::Downloaded(path))
+    }
+
+    fn wait_for_interval(&self) -> Result<(), Error> {
+        let last_request = match fs::read_to_string(self.inputs_dir.join(LAST_REQUEST_FILE)) {
+            Ok(millis) => millis.trim().parse().unwrap_or(0),
+            Err(_) => return Ok(()),
+        };
+        let since = now()?.saturating_sub(Duration::from_millis(last_request));
+        if since < self.min_interval {
+            thread::sleep(self.min_interval - since);
+        }
+        Ok(())
+    }
+
+    fn record_request(&self) -> Result<(), Error> {
+        fs::create_dir_all(&self.inputs_dir)?;
+        // Rounded up, so the wait before the next request is never cut short
+        let millis = now()?.as_nanos().div_ceil(1_000_000);
+        fs::write(self.inputs_dir.join(LAST_REQUEST_FILE), millis.to_string())?;
+        Ok(())
+    }
+}
+
+/// The session token from the AOC_SESSION environment variable, or otherwise from the session
+/// file in the config directory, ~/.config/advent_of_code/session.
+pub fn session_token() -> Result<String, Error> {
+    if let Ok(session) = env::var(SESSION_VAR) {
+        return Ok(session.trim().to_string());
+    }
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+        .ok_or_else(|| Error::Custom(format!("Set {} to your session token", SESSION_VAR)))?;
+    let path = config_dir.join("advent_of_code").join("session");
+    match fs::read_to_string(&path) {
+        Ok(session) => Ok(session.trim().to_string()),
+        Err(_) => Err(Error::Custom(format!(
+            "Set {} or put your session token in {}",
+            SESSION_VAR,
+            path.display()
+        ))),
+    }
+}
+
+fn now() -> Result<Duration, Error> {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| Error::Custom(e.to_string()))
+}
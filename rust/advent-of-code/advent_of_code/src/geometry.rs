@@ -0,0 +1,310 @@
+// Points on a square grid, and wires that run across it in straight segments. Where two wires
+// cross is found by intersecting every pair of segments, so it costs the same however long the
+// segments are. Segments that cross at right angles meet at a single point, but segments that
+// overlap along a row or column share every point of the overlap, so those still cost as much as
+// the overlap is long.
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub fn new(x: i32, y: i32) -> Self {
+        Point { x, y }
+    }
+
+    /// The distance between two points moving only along the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use advent_of_code::geometry::Point;
+    ///
+    /// assert_eq!(Point::new(3, -4).manhattan_distance(Point::ORIGIN), 7);
+    /// ```
+    pub fn manhattan_distance(self, other: Point) -> u64 {
+        (i64::from(self.x) - i64::from(other.x)).unsigned_abs()
+            + (i64::from(self.y) - i64::from(other.y)).unsigned_abs()
+    }
+
+    /// The point `distance` away in a direction, or None if that's past the edge of the grid.
+    pub fn moved(self, direction: Direction, distance: u32) -> Option<Point> {
+        let (dx, dy) = direction.delta();
+        let coordinate = |start: i32, delta: i32| {
+            i32::try_from(i64::from(start) + i64::from(delta) * i64::from(distance)).ok()
+        };
+        Some(Point::new(coordinate(self.x, dx)?, coordinate(self.y, dy)?))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    /// How x and y change for a step in this direction, with y increasing upwards.
+    pub fn delta(self) -> (i32, i32) {
+        match self {
+            Direction::Up => (0, 1),
+            Direction::Down => (0, -1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "U" => Ok(Direction::Up),
+            "D" => Ok(Direction::Down),
+            "L" => Ok(Direction::Left),
+            "R" => Ok(Direction::Right),
+            _ => Err(format!("{} isn't a direction, use U, D, L or R", s)),
+        }
+    }
+}
+
+/// A straight line from one point to another, along a row or column of the grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Segment {
+    pub start: Point,
+    pub end: Point,
+}
+
+impl Segment {
+    pub fn length(&self) -> u64 {
+        self.start.manhattan_distance(self.end)
+    }
+
+    pub fn is_horizontal(&self) -> bool {
+        self.start.y == self.end.y
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        between(point.x, self.start.x, self.end.x) && between(point.y, self.start.y, self.end.y)
+    }
+
+    /// The points both segments go through, which is more than one where they overlap.
+    pub fn intersections(&self, other: &Segment) -> Vec<Point> {
+        let (low_x, high_x) = overlap(self.start.x, self.end.x, other.start.x, other.end.x);
+        let (low_y, high_y) = overlap(self.start.y, self.end.y, other.start.y, other.end.y);
+        if low_x > high_x || low_y > high_y {
+            return Vec::new();
+        }
+        // Segments run along the grid, so the overlap is a single point or a line of them
+        (low_x..=high_x)
+            .flat_map(|x| (low_y..=high_y).map(move |y| Point::new(x, y)))
+            .collect()
+    }
+}
+
+/// Where two wires cross, and the combined steps both took to get there.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Crossing {
+    pub point: Point,
+    pub steps: usize,
+}
+
+/// A path from a start point made of moves in a direction for a distance.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Wire {
+    segments: Vec<Segment>,
+}
+
+impl Wire {
+    /// The wire that makes the moves in turn from start, or None if it goes past the edge of the
+    /// grid.
+    pub fn new(start: Point, moves: &[(Direction, u32)]) -> Option<Self> {
+        let mut position = start;
+        let segments = moves
+            .iter()
+            .map(|&(direction, distance)| {
+                let end = position.moved(direction, distance)?;
+                let segment = Segment {
+                    start: position,
+                    end,
+                };
+                position = end;
+                Some(segment)
+            })
+            .collect::<Option<_>>()?;
+        Some(Wire { segments })
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// Every point where the wires cross, by intersecting every pair of segments.
+    pub fn crossings(&self, other: &Wire) -> Vec<Crossing> {
+        let mut crossings: HashMap<Point, usize> = HashMap::new();
+        for (segment, to_segment) in self.segments.iter().zip(self.steps_to_segments()) {
+            for (other_segment, to_other_segment) in
+                other.segments.iter().zip(other.steps_to_segments())
+            {
+                for point in segment.intersections(other_segment) {
+                    let steps = to_segment + segment.start.manhattan_distance(point) as usize;
+                    let other_steps =
+                        to_other_segment + other_segment.start.manhattan_distance(point) as usize;
+                    // Where a wire starts isn't somewhere it's been to
+                    if steps == 0 || other_steps == 0 {
+                        continue;
+                    }
+                    let fewest = crossings.entry(point).or_insert(steps + other_steps);
+                    *fewest = (*fewest).min(steps + other_steps);
+                }
+            }
+        }
+        crossings
+            .into_iter()
+            .map(|(point, steps)| Crossing { point, steps })
+            .collect()
+    }
+
+    // The steps taken to reach the start of each segment
+    fn steps_to_segments(&self) -> impl Iterator<Item = usize> + '_ {
+        self.segments.iter().scan(0, |steps, segment| {
+            let start = *steps;
+            *steps += segment.length() as usize;
+            Some(start)
+        })
+    }
+}
+
+fn between(value: i32, a: i32, b: i32) -> bool {
+    a.min(b) <= value && value <= a.max(b)
+}
+
+// The range two ranges have in common, which is empty if the low end is above the high end
+fn overlap(a1: i32, a2: i32, b1: i32, b2: i32) -> (i32, i32) {
+    (a1.min(a2).max(b1.min(b2)), a1.max(a2).min(b1.max(b2)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Direction::*;
+
+    fn wire(start: Point, moves: &[(Direction, u32)]) -> Wire {
+        Wire::new(start, moves).unwrap()
+    }
+
+    fn sorted(mut crossings: Vec<Crossing>) -> Vec<Crossing> {
+        crossings.sort_by_key(|crossing| crossing.point);
+        crossings
+    }
+
+    #[test]
+    fn manhattan_distance_counts_both_axes() {
+        assert_eq!(Point::new(20, 20).manhattan_distance(Point::ORIGIN), 40);
+        assert_eq!(Point::new(-2, 3).manhattan_distance(Point::new(2, -3)), 10);
+        let corner = Point::new(i32::MIN, i32::MIN);
+        assert_eq!(
+            corner.manhattan_distance(Point::new(i32::MAX, i32::MAX)),
+            2 * u64::from(u32::MAX)
+        );
+    }
+
+    #[test]
+    fn moves_stop_at_the_edge_of_the_grid() {
+        let edge = Point::new(i32::MAX, 0);
+        assert_eq!(Point::ORIGIN.moved(Right, i32::MAX as u32), Some(edge));
+        assert_eq!(edge.moved(Right, 1), None);
+        assert_eq!(Point::ORIGIN.moved(Down, u32::MAX), None);
+        assert_eq!(
+            Wire::new(Point::ORIGIN, &[(Right, i32::MAX as u32), (Right, 1)]),
+            None
+        );
+    }
+
+    #[test]
+    fn directions_parse_from_their_initial() {
+        assert_eq!("L".parse(), Ok(Left));
+        assert!("X".parse::<Direction>().is_err());
+    }
+
+    #[test]
+    fn perpendicular_segments_cross_at_a_point() {
+        let across = Segment {
+            start: Point::new(0, 2),
+            end: Point::new(5, 2),
+        };
+        let down = Segment {
+            start: Point::new(3, 4),
+            end: Point::new(3, -1),
+        };
+        assert_eq!(across.intersections(&down), vec![Point::new(3, 2)]);
+        assert!(across.contains(Point::new(3, 2)));
+        assert!(!down.is_horizontal());
+    }
+
+    #[test]
+    fn overlapping_segments_share_every_point_in_the_overlap() {
+        let first = Segment {
+            start: Point::new(0, 0),
+            end: Point::new(4, 0),
+        };
+        let second = Segment {
+            start: Point::new(6, 0),
+            end: Point::new(3, 0),
+        };
+        assert_eq!(
+            first.intersections(&second),
+            vec![Point::new(3, 0), Point::new(4, 0)]
+        );
+        let apart = Segment {
+            start: Point::new(0, 1),
+            end: Point::new(4, 1),
+        };
+        assert!(first.intersections(&apart).is_empty());
+    }
+
+    #[test]
+    fn crossings_are_found_from_segments() {
+        let first = wire(Point::ORIGIN, &[(Right, 8), (Up, 5), (Left, 5), (Down, 3)]);
+        let second = wire(Point::ORIGIN, &[(Up, 7), (Right, 6), (Down, 4), (Left, 4)]);
+        let expected = vec![
+            Crossing {
+                point: Point::new(3, 3),
+                steps: 40,
+            },
+            Crossing {
+                point: Point::new(6, 5),
+                steps: 30,
+            },
+        ];
+        assert_eq!(sorted(first.crossings(&second)), expected);
+    }
+
+    #[test]
+    fn the_fewest_steps_to_a_crossing_are_kept() {
+        // The first wire reaches (2, 0) on its way out and again on its way back
+        let first = wire(Point::ORIGIN, &[(Right, 4), (Up, 1), (Left, 2), (Down, 1)]);
+        let second = wire(Point::new(2, -2), &[(Up, 3)]);
+        let expected = vec![
+            Crossing {
+                point: Point::new(2, 0),
+                steps: 4,
+            },
+            Crossing {
+                point: Point::new(2, 1),
+                steps: 10,
+            },
+        ];
+        assert_eq!(sorted(first.crossings(&second)), expected);
+    }
+}
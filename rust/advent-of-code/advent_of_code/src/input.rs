@@ -0,0 +1,87 @@
+// Finds the puzzle input for a day. It can be given as a path, or as `-` to read it from stdin,
+// otherwise it's read from <year>/day_<n>/input.txt in the inputs directory, e.g.
+// 2019/day_three/input.txt.
+use crate::error::Error;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// The inputs directory in this crate, so the inputs are found wherever the binary is run from.
+pub const DEFAULT_INPUTS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/inputs");
+
+const DAY_NAMES: [&str; 25] = [
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+    "twenty",
+    "twenty_one",
+    "twenty_two",
+    "twenty_three",
+    "twenty_four",
+    "twenty_five",
+];
+
+/// The name of a day as it's written in module and directory names, e.g. `twenty_one`.
+pub fn day_name(day: usize) -> Result<&'static str, Error> {
+    day.checked_sub(1)
+        .and_then(|index| DAY_NAMES.get(index))
+        .copied()
+        .ok_or_else(|| Error::Custom(format!("There's no day {}", day)))
+}
+
+/// The directory in the inputs directory that holds a day's input.
+pub fn day_dir(inputs_dir: &Path, year: u32, day: usize) -> Result<PathBuf, Error> {
+    Ok(inputs_dir
+        .join(year.to_string())
+        .join(format!("day_{}", day_name(day)?)))
+}
+
+/// Where a day's input lives in the inputs directory.
+///
+/// # Examples
+///
+/// ```
+/// use advent_of_code::input::default_path;
+/// use std::path::Path;
+/// let path = default_path(Path::new("inputs"), 2019, 3).unwrap();
+///
+/// assert_eq!(path, Path::new("inputs/2019/day_three/input.txt"));
+/// ```
+pub fn default_path(inputs_dir: &Path, year: u32, day: usize) -> Result<PathBuf, Error> {
+    Ok(day_dir(inputs_dir, year, day)?.join("input.txt"))
+}
+
+pub fn read_input(
+    year: u32,
+    day: usize,
+    path: Option<&Path>,
+    inputs_dir: &Path,
+) -> Result<String, Error> {
+    let path = match path {
+        Some(path) if path == Path::new("-") => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            return Ok(input);
+        }
+        Some(path) => path.to_path_buf(),
+        None => default_path(inputs_dir, year, day)?,
+    };
+    fs::read_to_string(&path)
+        .map_err(|e| Error::Custom(format!("Couldn't read {}: {}", path.display(), e)))
+}
@@ -1,12 +1,13 @@
+pub mod answers;
 pub mod computer;
-pub mod day_four;
-pub mod day_one;
-pub mod day_three;
-pub mod day_two;
 pub mod error;
-pub mod day_five;
-
-#[macro_use]
-extern crate custom_derive;
-#[macro_use]
-extern crate enum_derive;
+pub mod fetch;
+pub mod geometry;
+pub mod input;
+pub mod parse;
+pub mod registry;
+pub mod render;
+pub mod scaffold;
+pub mod solution;
+pub mod timing;
+pub mod year_2019;
@@ -1,33 +1,403 @@
-use std::path::PathBuf;
+use std::fs;
+use std::io::{self, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::str::FromStr;
 
+use advent_of_code::answers::{self, Answers};
+use advent_of_code::computer::ascii::Ascii;
+use advent_of_code::computer::debugger::Debugger;
+use advent_of_code::computer::{disassembler, parse_program, Computer, ComputerActions, Status};
 use advent_of_code::error::Error;
+use advent_of_code::fetch::{self, Fetched, Fetcher};
+use advent_of_code::input::{self, read_input, DEFAULT_INPUTS_DIR};
+use advent_of_code::registry::{self, Puzzle};
+use advent_of_code::render::Format;
+use advent_of_code::scaffold;
+use advent_of_code::solution::{Part, BOTH_PARTS};
+use advent_of_code::timing::{self, Report};
 use structopt::StructOpt;
 #[derive(Debug, StructOpt)]
 struct Opt {
-    /// Day
-    day: usize,
-    /// Optional path to input file; if not supplied will read from stdin
+    /// Day, or all to run every solved day of the year
+    day: Option<DaySelection>,
+    /// Path to the puzzle input, or - to read it from stdin; if not supplied will read the
+    /// day's input from the inputs directory
     input: Option<PathBuf>,
+    /// Directory holding each day's input as <year>/day_<n>/input.txt
+    #[structopt(long, global = true, env = "AOC_INPUTS_DIR", default_value = DEFAULT_INPUTS_DIR)]
+    inputs_dir: PathBuf,
+    /// Only solve this part of the puzzle, 1 or 2
+    #[structopt(short, long, global = true)]
+    part: Option<Part>,
+    /// Year of the puzzle; defaults to the latest year with a solved day
+    #[structopt(short, long, global = true)]
+    year: Option<u32>,
+    /// Print answers and times as JSON
+    #[structopt(long, global = true)]
+    json: bool,
+    #[structopt(subcommand)]
+    command: Option<Command>,
 }
 
-fn main() -> Result<(), Error> {
-    let opt = Opt::from_args();
-    let answers = match opt.day {
-        1 => advent_of_code::day_one::run()?,
-        2 => advent_of_code::day_two::run()?,
-        3 => advent_of_code::day_three::run()?,
-        4 => advent_of_code::day_four::run()?,
-        5 => advent_of_code::day_five::run()?,
-        _ => return Err(Error::Custom(String::from("No valid day given"))),
+#[derive(Debug, StructOpt)]
+enum Command {
+    /// List the days that have been solved in each year
+    List,
+    /// Add a module for a new day, wired into the registry, and a directory for its input
+    New {
+        /// Day of the puzzle; the year is given with --year
+        day: usize,
+    },
+    /// Download a day's input into the inputs directory, using the session token from
+    /// AOC_SESSION or ~/.config/advent_of_code/session
+    Fetch {
+        /// Day, or all to fetch every solved day of the year
+        day: DaySelection,
+    },
+    /// Time a day's solution over repeated runs
+    Bench {
+        /// Day, or all to benchmark every solved day of the year
+        day: DaySelection,
+        /// Runs to make before timing starts
+        #[structopt(long, default_value = "3")]
+        warmup: usize,
+        /// Timed runs
+        #[structopt(short, long, default_value = "10")]
+        runs: usize,
+    },
+    /// Draw a day's state, as ASCII art if it's small enough and otherwise as an SVG
+    Render {
+        day: usize,
+        /// Path to the puzzle input; if not supplied will read the day's input from the inputs
+        /// directory
+        input: Option<PathBuf>,
+        /// auto, ascii, svg or ppm; auto goes by the output's extension, or else the size
+        #[structopt(short, long, default_value = "auto")]
+        format: Format,
+        /// File to write the drawing to; if not supplied will write to stdout
+        #[structopt(short, long)]
+        output: Option<PathBuf>,
+        /// Colour ASCII art with terminal escape codes
+        #[structopt(long)]
+        colour: bool,
+    },
+    /// Print an annotated listing of an Intcode program
+    Disassemble {
+        /// Optional path to the program; if not supplied will read from stdin
+        program: Option<PathBuf>,
+    },
+    /// Step through an Intcode program with breakpoints and watchpoints
+    Debug {
+        /// Path to the program
+        program: PathBuf,
+        /// Inputs to queue for the program before it starts
+        #[structopt(short, long, allow_hyphen_values = true)]
+        input: Vec<i64>,
+    },
+    /// Play an Intcode program that reads and prints ASCII text
+    Ascii {
+        /// Path to the program
+        program: PathBuf,
+        /// Files of commands, one per line, to send before reading from stdin
+        #[structopt(short, long)]
+        script: Vec<PathBuf>,
+    },
+}
+
+fn main() {
+    if let Err(error) = run(Opt::from_args()) {
+        eprintln!("Error: {}", error);
+        process::exit(1);
+    }
+}
+
+fn run(opt: Opt) -> Result<(), Error> {
+    let parts = match opt.part {
+        Some(part) => vec![part],
+        None => BOTH_PARTS.to_vec(),
+    };
+    match opt.command {
+        Some(Command::Disassemble { program }) => disassemble(program),
+        Some(Command::Debug { program, input }) => debug(program, input),
+        Some(Command::Ascii { program, script }) => ascii(program, script),
+        Some(Command::List) => {
+            list();
+            Ok(())
+        }
+        Some(Command::New { day }) => new_day(opt.year, day, &opt.inputs_dir),
+        Some(Command::Fetch { day }) => {
+            let puzzles = select_puzzles(day, opt.year)?;
+            fetch(&puzzles, &opt.inputs_dir)
+        }
+        Some(Command::Bench { day, warmup, runs }) => {
+            let puzzles = select_puzzles(day, opt.year)?;
+            bench(&puzzles, &opt.inputs_dir, &parts, warmup, runs, opt.json)
+        }
+        Some(Command::Render {
+            day,
+            input,
+            format,
+            output,
+            colour,
+        }) => {
+            let puzzle = select_puzzles(DaySelection::Day(day), opt.year)?[0];
+            render(puzzle, input, &opt.inputs_dir, format, output, colour)
+        }
+        None => {
+            let day = opt
+                .day
+                .ok_or_else(|| Error::Custom(String::from("No day given")))?;
+            if let (DaySelection::All, Some(_)) = (&day, &opt.input) {
+                return Err(Error::Custom(String::from(
+                    "An input can't be given when running all days",
+                )));
+            }
+            let puzzles = select_puzzles(day, opt.year)?;
+            run_days(&puzzles, opt.input, &opt.inputs_dir, &parts, opt.json)
+        }
+    }
+}
+
+fn new_day(year: Option<u32>, day: usize, inputs_dir: &Path) -> Result<(), Error> {
+    let year = match year {
+        Some(year) => year,
+        None => latest_year()?,
+    };
+    let changed = scaffold::new_day(Path::new(scaffold::SRC_DIR), inputs_dir, year, day)?;
+    scaffold::format(&changed)?;
+    for path in changed {
+        println!("{}", path.display());
+    }
+    println!(
+        "\nAdd the puzzle input to {} and its answers to {} once they're known to be right",
+        input::default_path(inputs_dir, year, day)?.display(),
+        answers::default_path(inputs_dir).display()
+    );
+    Ok(())
+}
+
+fn latest_year() -> Result<u32, Error> {
+    registry::years()
+        .last()
+        .copied()
+        .ok_or_else(|| Error::Custom(String::from("No puzzles have been solved")))
+}
+
+// The puzzles to run from the given year, or the latest year with a solved day
+fn select_puzzles(day: DaySelection, year: Option<u32>) -> Result<Vec<&'static Puzzle>, Error> {
+    let year = match year {
+        Some(year) => year,
+        None => latest_year()?,
     };
+    match day {
+        DaySelection::Day(day) => registry::find(year, day)
+            .map(|puzzle| vec![puzzle])
+            .ok_or_else(|| Error::Custom(format!("Day {} of {} hasn't been solved", day, year))),
+        DaySelection::All => Ok(registry::days(year)),
+    }
+}
+
+#[derive(Debug)]
+enum DaySelection {
+    All,
+    Day(usize),
+}
+
+impl FromStr for DaySelection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "all" => Ok(DaySelection::All),
+            _ => s
+                .parse()
+                .map(DaySelection::Day)
+                .map_err(|_| format!("{} isn't a day, use a number or all", s)),
+        }
+    }
+}
+
+fn list() {
+    for year in registry::years() {
+        let days: Vec<String> = registry::days(year)
+            .iter()
+            .map(|puzzle| puzzle.day.to_string())
+            .collect();
+        println!("{}: {}", year, days.join(", "));
+    }
+}
 
-    println!("\nDay: {day}", day = opt.day);
-    for (i, answer) in answers.iter().enumerate() {
+fn disassemble(program: Option<PathBuf>) -> Result<(), Error> {
+    let program = parse_program(&read_to_string(program)?)?;
+    print!("{}", disassembler::listing(&program));
+    Ok(())
+}
+
+fn debug(program: PathBuf, inputs: Vec<i64>) -> Result<(), Error> {
+    let mut computer = Computer::new(0, 0, parse_program(&fs::read_to_string(program)?)?);
+    for input in inputs {
+        computer.push_input(input);
+    }
+    let stdin = io::stdin();
+    Debugger::new(computer).session(stdin.lock(), &mut io::stdout())?;
+    Ok(())
+}
+
+fn ascii(program: PathBuf, scripts: Vec<PathBuf>) -> Result<(), Error> {
+    let computer = Computer::new(0, 0, parse_program(&fs::read_to_string(program)?)?);
+    let mut ascii = Ascii::new(computer);
+    let mut stdout = io::stdout();
+    for script in scripts {
+        let script = BufReader::new(fs::File::open(script)?);
+        if ascii.session(script, &mut stdout, true)? != Status::NeedsInput {
+            return Ok(());
+        }
+    }
+    let stdin = io::stdin();
+    ascii.session(stdin.lock(), &mut stdout, false)?;
+    Ok(())
+}
+
+fn read_to_string(path: Option<PathBuf>) -> Result<String, Error> {
+    match path {
+        Some(path) => Ok(fs::read_to_string(path)?),
+        None => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            Ok(input)
+        }
+    }
+}
+
+fn run_days(
+    puzzles: &[&Puzzle],
+    input: Option<PathBuf>,
+    inputs_dir: &Path,
+    parts: &[Part],
+    json: bool,
+) -> Result<(), Error> {
+    // The recorded answers are for the inputs in the inputs directory, not for a given input
+    let answers = match input {
+        Some(_) => Answers::default(),
+        None => Answers::load(&answers::default_path(inputs_dir))?,
+    };
+    let mut reports = Vec::new();
+    for puzzle in puzzles {
+        let input = read_input(puzzle.year, puzzle.day, input.as_deref(), inputs_dir)?;
+        let mut report = Report::new(puzzle, (puzzle.solve)(&input, parts)?);
+        report.check(&answers);
+        if !json {
+            print_report(&report);
+        }
+        reports.push(report);
+    }
+    if json {
+        println!("{}", serde_json::to_string_pretty(&reports)?);
+    }
+    match reports.iter().map(Report::incorrect).sum() {
+        0 => Ok(()),
+        1 => Err(Error::Custom(String::from("1 answer was incorrect"))),
+        incorrect => Err(Error::Custom(format!(
+            "{} answers were incorrect",
+            incorrect
+        ))),
+    }
+}
+
+fn print_report(report: &Report) {
+    println!("\nDay: {day}", day = report.day);
+    println!("Parsed in {:?}\n", report.parse_time);
+    for part in &report.parts {
         println!(
-            "Answer {number}: {answer}\n",
-            number = i + 1,
-            answer = answer
+            "Answer {number}: {answer} ({time:?}) [{verdict}]\n",
+            number = part.part,
+            answer = part.answer,
+            time = part.time,
+            verdict = part.verdict
         )
     }
+}
+
+fn render(
+    puzzle: &Puzzle,
+    input: Option<PathBuf>,
+    inputs_dir: &Path,
+    format: Format,
+    output: Option<PathBuf>,
+    colour: bool,
+) -> Result<(), Error> {
+    let render = puzzle.render.ok_or_else(|| {
+        Error::Custom(format!(
+            "Day {} of {} can't be drawn",
+            puzzle.day, puzzle.year
+        ))
+    })?;
+    let input = read_input(puzzle.year, puzzle.day, input.as_deref(), inputs_dir)?;
+    let scene = render(&input)?;
+    let extension = output
+        .as_ref()
+        .and_then(|path| path.extension())
+        .and_then(|extension| extension.to_str());
+    let format = match (format, extension) {
+        (Format::Auto, Some("txt")) => Format::Ascii,
+        (Format::Auto, Some("svg")) => Format::Svg,
+        (Format::Auto, Some("ppm")) => Format::Ppm,
+        (Format::Auto, Some(extension)) => {
+            return Err(Error::Custom(format!(
+                "Can't draw a .{} file, use .txt, .svg or .ppm or give a --format",
+                extension
+            )))
+        }
+        (format, _) => format,
+    };
+    let drawing = scene.draw(format, colour)?;
+    match output {
+        Some(path) => fs::write(path, drawing)?,
+        None => io::stdout().write_all(&drawing)?,
+    }
+    Ok(())
+}
+
+fn fetch(puzzles: &[&Puzzle], inputs_dir: &Path) -> Result<(), Error> {
+    let fetcher = Fetcher::new(fetch::session_token()?, inputs_dir);
+    for puzzle in puzzles {
+        match fetcher.fetch(puzzle.year, puzzle.day)? {
+            Fetched::Cached(path) => println!("Already have {}", path.display()),
+            Fetched::Downloaded(path) => println!("Downloaded {}", path.display()),
+        }
+    }
+    Ok(())
+}
+
+fn bench(
+    puzzles: &[&Puzzle],
+    inputs_dir: &Path,
+    parts: &[Part],
+    warmup: usize,
+    runs: usize,
+    json: bool,
+) -> Result<(), Error> {
+    let mut benchmarks = Vec::new();
+    for puzzle in puzzles {
+        let input = read_input(puzzle.year, puzzle.day, None, inputs_dir)?;
+        let benchmark = timing::bench(puzzle, &input, parts, warmup, runs)?;
+        if !json {
+            println!(
+                "\nDay {day} ({runs} runs after {warmup} warmup)",
+                day = benchmark.day,
+                runs = benchmark.runs,
+                warmup = benchmark.warmup
+            );
+            println!("  Parse:  {}", benchmark.parse);
+            for part in &benchmark.parts {
+                println!("  Part {}: {}", part.part, part.stats);
+            }
+        }
+        benchmarks.push(benchmark);
+    }
+    if json {
+        println!("{}", serde_json::to_string_pretty(&benchmarks)?);
+    }
     Ok(())
 }
@@ -0,0 +1,329 @@
+// Parsers for puzzle inputs. A piece of input is an `Input`, which knows the line and column it
+// starts at, so anything that can't be parsed is reported with where it is instead of panicking.
+// The combinators split an input into lines, separated lists, records and grids, and hand each
+// piece to a parser for the items in it.
+use crate::error::ParseError;
+use std::any;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Input<'a> {
+    pub text: &'a str,
+    /// The line the text starts on, counting from 1.
+    pub line: usize,
+    /// The column the text starts at, counting characters from 1.
+    pub column: usize,
+}
+
+impl<'a> Input<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Input {
+            text,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError {
+            line: self.line,
+            column: self.column,
+            message: message.into(),
+        }
+    }
+
+    /// The text without leading and trailing whitespace, positioned at its first character.
+    pub fn trim(&self) -> Input<'a> {
+        let text = self.text.trim_start();
+        self.slice(self.text.len() - text.len(), text.trim_end())
+    }
+
+    /// Parses the trimmed text as a `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use advent_of_code::parse::Input;
+    /// let error = Input::new("12\n 3x").lines().nth(1).unwrap().parse::<i32>().unwrap_err();
+    ///
+    /// assert_eq!(error.to_string(), "\"3x\" isn't a valid i32 at line 2, column 2");
+    /// ```
+    pub fn parse<T: FromStr>(&self) -> Result<T, ParseError> {
+        let input = self.trim();
+        input.text.parse().map_err(|_| {
+            input.error(format!(
+                "{:?} isn't a valid {}",
+                input.text,
+                short_type_name::<T>()
+            ))
+        })
+    }
+
+    /// Splits the text on a separator, keeping the position of each piece.
+    pub fn split(&self, separator: char) -> impl Iterator<Item = Input<'a>> {
+        let input = *self;
+        let mut start = 0;
+        self.text.split(separator).map(move |piece| {
+            let piece_input = input.slice(start, piece);
+            start += piece.len() + separator.len_utf8();
+            piece_input
+        })
+    }
+
+    /// Each line of the text, without its line ending.
+    pub fn lines(&self) -> impl Iterator<Item = Input<'a>> {
+        self.split('\n').map(|line| Input {
+            text: line.text.strip_suffix('\r').unwrap_or(line.text),
+            ..line
+        })
+    }
+
+    /// Splits the text in two around the first separator.
+    pub fn split_once(&self, separator: char) -> Result<(Input<'a>, Input<'a>), ParseError> {
+        let index = self
+            .text
+            .find(separator)
+            .ok_or_else(|| self.error(format!("Expected {:?} in {:?}", separator, self.text)))?;
+        Ok((
+            self.slice(0, &self.text[..index]),
+            self.slice(
+                index + separator.len_utf8(),
+                &self.text[index + separator.len_utf8()..],
+            ),
+        ))
+    }
+
+    /// Splits the text in two after its first `characters` characters.
+    pub fn split_at(&self, characters: usize) -> Result<(Input<'a>, Input<'a>), ParseError> {
+        let index = match self.text.char_indices().nth(characters) {
+            Some((index, _)) => index,
+            None => {
+                return Err(self.error(format!(
+                    "Expected more than {} characters in {:?}",
+                    characters, self.text
+                )))
+            }
+        };
+        Ok((
+            self.slice(0, &self.text[..index]),
+            self.slice(index, &self.text[index..]),
+        ))
+    }
+
+    // The part of the text that starts `offset` bytes in
+    fn slice(&self, offset: usize, text: &'a str) -> Input<'a> {
+        let skipped = &self.text[..offset];
+        match skipped.rfind('\n') {
+            Some(newline) => Input {
+                text,
+                line: self.line + skipped.matches('\n').count(),
+                column: skipped[newline + 1..].chars().count() + 1,
+            },
+            None => Input {
+                text,
+                line: self.line,
+                column: self.column + skipped.chars().count(),
+            },
+        }
+    }
+}
+
+/// A rectangle of cells, read a row at a time from the top left.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grid<T> {
+    pub width: usize,
+    pub height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x < self.width && y < self.height {
+            self.cells.get(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+}
+
+/// Parses each line that isn't blank with `item`.
+pub fn lines<'a, T>(
+    input: Input<'a>,
+    item: impl FnMut(Input<'a>) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .filter(|line| !line.text.trim().is_empty())
+        .map(item)
+        .collect()
+}
+
+/// Parses each piece between separators with `item`.
+pub fn separated<'a, T>(
+    input: Input<'a>,
+    separator: char,
+    item: impl FnMut(Input<'a>) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input.split(separator).map(item).collect()
+}
+
+/// A list of values separated by commas, e.g. an Intcode program.
+///
+/// # Examples
+///
+/// ```
+/// use advent_of_code::parse::{comma_list, Input};
+/// let values: Vec<i64> = comma_list(Input::new("1,-2, 3\n")).unwrap();
+///
+/// assert_eq!(values, vec![1, -2, 3]);
+/// ```
+pub fn comma_list<T: FromStr>(input: Input) -> Result<Vec<T>, ParseError> {
+    separated(input.trim(), ',', |value| value.parse())
+}
+
+/// Two values either side of a separator, e.g. a range written as 10-20.
+pub fn pair<A: FromStr, B: FromStr>(input: Input, separator: char) -> Result<(A, B), ParseError> {
+    let (first, second) = input.trim().split_once(separator)?;
+    Ok((first.parse()?, second.parse()?))
+}
+
+/// Parses each group of lines, with blank lines between the groups, with `item`.
+pub fn records<'a, T>(
+    input: Input<'a>,
+    mut item: impl FnMut(Input<'a>) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    let mut records = Vec::new();
+    // Where the record being read starts and ends, in bytes
+    let mut record: Option<(usize, usize)> = None;
+    let mut offset = 0;
+    for line in input.text.split('\n') {
+        let end = offset + line.trim_end_matches('\r').len();
+        if !line.trim().is_empty() {
+            record = Some((record.map_or(offset, |(start, _)| start), end));
+        } else if let Some((start, end)) = record.take() {
+            records.push(item(input.slice(start, &input.text[start..end]))?);
+        }
+        offset += line.len() + 1;
+    }
+    if let Some((start, end)) = record {
+        records.push(item(input.slice(start, &input.text[start..end]))?);
+    }
+    Ok(records)
+}
+
+/// Reads every character of a rectangle of lines into a cell, or fails on a character `cell`
+/// gives None for.
+pub fn grid<T>(
+    input: Input,
+    mut cell: impl FnMut(char) -> Option<T>,
+) -> Result<Grid<T>, ParseError> {
+    let mut cells = Vec::new();
+    let mut width = None;
+    let mut height = 0;
+    for line in input.lines().filter(|line| !line.text.trim().is_empty()) {
+        let mut line_width = 0;
+        for (index, character) in line.text.char_indices() {
+            let value = cell(character).ok_or_else(|| {
+                line.slice(index, &line.text[index..])
+                    .error(format!("Unexpected {:?} in grid", character))
+            })?;
+            cells.push(value);
+            line_width += 1;
+        }
+        match width {
+            Some(width) if width != line_width => {
+                return Err(line.error(format!(
+                    "Expected a row of {} cells, found {}",
+                    width, line_width
+                )))
+            }
+            _ => width = Some(line_width),
+        }
+        height += 1;
+    }
+    Ok(Grid {
+        width: width.unwrap_or(0),
+        height,
+        cells,
+    })
+}
+
+// The name of a type without its module path, e.g. i32 or Direction
+fn short_type_name<T>() -> &'static str {
+    let name = any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_skip_blank_lines_and_trailing_newlines() {
+        let values: Vec<i32> = lines(Input::new("1\n\n2\r\n3\n"), |line| line.parse()).unwrap();
+        assert_eq!(values, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn errors_point_at_the_bad_value() {
+        let error = lines(Input::new("1,2\n3,x,5\n"), |line| comma_list::<i32>(line)).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.message, "\"x\" isn't a valid i32");
+    }
+
+    #[test]
+    fn split_at_and_split_once_keep_positions() {
+        let (direction, distance) = Input::new("R75").split_at(1).unwrap();
+        assert_eq!(direction.text, "R");
+        assert_eq!((distance.text, distance.column), ("75", 2));
+        assert!(Input::new("R").split_at(1).is_err());
+
+        let (start, end) = Input::new("  10 - 20").split_once('-').unwrap();
+        assert_eq!(start.trim().column, 3);
+        assert_eq!(end.trim().column, 8);
+    }
+
+    #[test]
+    fn pairs() {
+        assert_eq!(
+            pair::<i32, i32>(Input::new("234208-765869\n"), '-'),
+            Ok((234208, 765869))
+        );
+        let error = pair::<i32, i32>(Input::new("234208"), '-').unwrap_err();
+        assert_eq!(error.message, "Expected '-' in \"234208\"");
+    }
+
+    #[test]
+    fn records_are_separated_by_blank_lines() {
+        let input = Input::new("a b\nc\n\n\nd\n  \ne f\n");
+        let records = records(input, |record| Ok((record.text, record.line))).unwrap();
+        assert_eq!(records, vec![("a b\nc", 1), ("d", 5), ("e f", 7)]);
+    }
+
+    #[test]
+    fn grids() {
+        let grid = grid(Input::new("#.\n.#\n"), |cell| match cell {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!((grid.width, grid.height), (2, 2));
+        assert_eq!(grid.get(1, 1), Some(&true));
+        assert_eq!(grid.get(2, 0), None);
+        assert_eq!(grid.rows().next(), Some(&[true, false][..]));
+    }
+
+    #[test]
+    fn grids_must_be_rectangles_of_known_cells() {
+        let cell = |c: char| c.to_digit(10);
+        let error = grid(Input::new("12\n3a\n"), cell).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        let error = grid(Input::new("12\n345\n"), cell).unwrap_err();
+        assert_eq!(error.message, "Expected a row of 2 cells, found 3");
+    }
+}
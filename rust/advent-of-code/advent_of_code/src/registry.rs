@@ -0,0 +1,41 @@
+// Every puzzle that has been solved, so the CLI can look them up by year and day. Each year is a
+// module with its days as submodules, and lists its puzzles in its PUZZLES.
+use crate::error::Error;
+use crate::render::Scene;
+use crate::solution::{Part, Solved};
+
+/// Parses an input and builds a picture of it, for days that can be drawn.
+pub type Renderer = fn(&str) -> Result<Scene, Error>;
+
+pub struct Puzzle {
+    pub year: u32,
+    pub day: usize,
+    /// Parses the input and solves the given parts.
+    pub solve: fn(&str, &[Part]) -> Result<Solved, Error>,
+    pub render: Option<Renderer>,
+}
+
+const YEARS: &[&[Puzzle]] = &[crate::year_2019::PUZZLES];
+
+pub fn puzzles() -> impl Iterator<Item = &'static Puzzle> {
+    YEARS.iter().flat_map(|puzzles| puzzles.iter())
+}
+
+pub fn find(year: u32, day: usize) -> Option<&'static Puzzle> {
+    puzzles().find(|puzzle| puzzle.year == year && puzzle.day == day)
+}
+
+/// The puzzles solved in a year, in order of day.
+pub fn days(year: u32) -> Vec<&'static Puzzle> {
+    let mut days: Vec<&Puzzle> = puzzles().filter(|puzzle| puzzle.year == year).collect();
+    days.sort_by_key(|puzzle| puzzle.day);
+    days
+}
+
+/// Every year with at least one solved puzzle, earliest first.
+pub fn years() -> Vec<u32> {
+    let mut years: Vec<u32> = puzzles().map(|puzzle| puzzle.year).collect();
+    years.sort_unstable();
+    years.dedup();
+    years
+}
@@ -0,0 +1,489 @@
+// Pictures of a puzzle's state on the grid, for seeing what a solution is doing. A day builds a
+// `Scene` of coloured paths and markers from its input, which can be drawn as ASCII art for small
+// inputs, or as an SVG or PPM image for inputs too big to read as text. The y axis points up, as
+// it does in `geometry`, so rows are drawn from the top of the scene down.
+use crate::error::Error;
+use crate::geometry::Point;
+use crate::solution::Solution;
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::fmt::Write;
+use std::str::FromStr;
+
+/// The largest scene, in cells, that can be drawn as ASCII art. `Auto` draws anything bigger as
+/// an SVG.
+pub const MAX_ASCII_WIDTH: usize = 120;
+pub const MAX_ASCII_HEIGHT: usize = 60;
+
+// The longest side of an SVG or PPM image, in pixels
+const MAX_IMAGE_SIZE: usize = 1000;
+
+// The most pixels a cell takes up in a PPM image of a small scene
+const MAX_CELL_SIZE: usize = 8;
+
+// The cells or pixels of blank space left around the scene
+const MARGIN: i64 = 1;
+const IMAGE_MARGIN: usize = 10;
+
+/// A day whose state can be drawn.
+pub trait Render: Solution {
+    fn render(input: &Self::Input) -> Result<Scene, Error>;
+}
+
+/// Parses the input and builds the scene for it.
+pub fn scene<S: Render>(input: &str) -> Result<Scene, Error> {
+    S::render(&S::parse(input)?)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Colour {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+impl Colour {
+    pub const BLACK: Colour = Colour::new(0, 0, 0);
+    pub const WHITE: Colour = Colour::new(255, 255, 255);
+    pub const RED: Colour = Colour::new(220, 50, 47);
+    pub const BLUE: Colour = Colour::new(38, 139, 210);
+    pub const GREEN: Colour = Colour::new(0, 160, 60);
+    pub const MAGENTA: Colour = Colour::new(211, 54, 130);
+    pub const GREY: Colour = Colour::new(120, 120, 120);
+
+    pub const fn new(red: u8, green: u8, blue: u8) -> Self {
+        Colour { red, green, blue }
+    }
+
+    fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.red, self.green, self.blue)
+    }
+
+    // The escape code that sets a terminal's text to this colour
+    fn ansi(self) -> String {
+        format!("\x1b[38;2;{};{};{}m", self.red, self.green, self.blue)
+    }
+}
+
+/// A line through a list of points, each in a straight line along the grid from the last.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Path {
+    pub points: Vec<Point>,
+    pub colour: Colour,
+}
+
+/// A point picked out of the scene, drawn as `glyph` in ASCII art and as a dot in images.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Marker {
+    pub point: Point,
+    pub glyph: char,
+    pub colour: Colour,
+    /// What the marker shows, listed in the key under ASCII art and as a tooltip in an SVG.
+    pub label: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Scene {
+    pub paths: Vec<Path>,
+    /// Markers are drawn over the paths, later markers over earlier ones.
+    pub markers: Vec<Marker>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// ASCII art for scenes that fit in a terminal, and an SVG for anything bigger.
+    Auto,
+    Ascii,
+    Svg,
+    Ppm,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(Format::Auto),
+            "ascii" => Ok(Format::Ascii),
+            "svg" => Ok(Format::Svg),
+            "ppm" => Ok(Format::Ppm),
+            _ => Err(format!("{} isn't a format, use auto, ascii, svg or ppm", s)),
+        }
+    }
+}
+
+// The smallest and largest x and y of everything in a scene, and how many cells that spans
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Bounds {
+    min: Point,
+    max: Point,
+    width: usize,
+    height: usize,
+}
+
+impl Scene {
+    /// The format `Auto` stands for with this scene: ASCII art if it fits in a terminal.
+    pub fn resolve(&self, format: Format) -> Result<Format, Error> {
+        Ok(match format {
+            Format::Auto => {
+                let bounds = self.bounds()?;
+                if bounds.width <= MAX_ASCII_WIDTH && bounds.height <= MAX_ASCII_HEIGHT {
+                    Format::Ascii
+                } else {
+                    Format::Svg
+                }
+            }
+            format => format,
+        })
+    }
+
+    /// The scene drawn in a format. ASCII art is coloured with terminal escape codes if `colour`
+    /// is set.
+    pub fn draw(&self, format: Format, colour: bool) -> Result<Vec<u8>, Error> {
+        Ok(match self.resolve(format)? {
+            Format::Ascii | Format::Auto => self.ascii(colour)?.into_bytes(),
+            Format::Svg => self.svg()?.into_bytes(),
+            Format::Ppm => self.ppm()?,
+        })
+    }
+
+    /// The scene as rows of characters with a key to the markers below, in the style of the
+    /// puzzle descriptions: `-` and `|` along paths, `+` where they turn and `.` elsewhere.
+    /// Scenes bigger than `MAX_ASCII_WIDTH` by `MAX_ASCII_HEIGHT` are an error, as they'd be too
+    /// big to read and could take gigabytes to draw.
+    pub fn ascii(&self, colour: bool) -> Result<String, Error> {
+        let bounds = self.bounds()?;
+        if bounds.width > MAX_ASCII_WIDTH || bounds.height > MAX_ASCII_HEIGHT {
+            return Err(Error::Custom(format!(
+                "The scene is {} by {}, too big for ASCII art, which can be at most {} by {}; \
+                 draw it as an SVG or PPM instead",
+                bounds.width, bounds.height, MAX_ASCII_WIDTH, MAX_ASCII_HEIGHT
+            )));
+        }
+        // In i64, as the margin can be past the edge of the grid
+        let (min_x, min_y) = (
+            i64::from(bounds.min.x) - MARGIN,
+            i64::from(bounds.min.y) - MARGIN,
+        );
+        let (width, height) = (
+            bounds.width + 2 * MARGIN as usize,
+            bounds.height + 2 * MARGIN as usize,
+        );
+        let mut cells: Vec<(char, Option<Colour>)> = vec![('.', None); width * height];
+        let index = |point: Point| {
+            let (x, y) = (i64::from(point.x) - min_x, i64::from(point.y) - min_y);
+            (height - 1 - y as usize) * width + x as usize
+        };
+
+        for path in &self.paths {
+            for (i, pair) in path.points.windows(2).enumerate() {
+                let (start, end) = (pair[0], pair[1]);
+                let glyph = if start.y == end.y { '-' } else { '|' };
+                for point in line(start, end) {
+                    cells[index(point)] = (glyph, Some(path.colour));
+                }
+                // Where the path turns, or carries on from the previous line
+                if i > 0 {
+                    cells[index(start)] = ('+', Some(path.colour));
+                }
+            }
+        }
+        for marker in &self.markers {
+            cells[index(marker.point)] = (marker.glyph, Some(marker.colour));
+        }
+
+        let mut art = String::new();
+        for row in cells.chunks(width) {
+            for &(glyph, cell_colour) in row {
+                match cell_colour {
+                    Some(cell_colour) if colour => {
+                        art.push_str(&cell_colour.ansi());
+                        art.push(glyph);
+                        art.push_str("\x1b[0m");
+                    }
+                    _ => art.push(glyph),
+                }
+            }
+            art.push('\n');
+        }
+        let mut keyed = HashSet::new();
+        for marker in &self.markers {
+            if keyed.insert((marker.glyph, &marker.label)) {
+                writeln!(art, "{} {}", marker.glyph, marker.label).unwrap();
+            }
+        }
+        Ok(art)
+    }
+
+    /// The scene as an SVG image, scaled so its longest side is at most 1000 pixels.
+    pub fn svg(&self) -> Result<String, Error> {
+        let bounds = self.bounds()?;
+        let longest = bounds.width.max(bounds.height) as f64;
+        let scale = (MAX_IMAGE_SIZE as f64 / longest).min(MAX_CELL_SIZE as f64);
+        // In cells, so the lines and dots are the same size on the image whatever the scale
+        let stroke = 1.0 / scale;
+        let radius = 3.0 / scale;
+        let margin = IMAGE_MARGIN as f64 / scale;
+        let (width, height) = (
+            bounds.width as f64 + 2.0 * margin,
+            bounds.height as f64 + 2.0 * margin,
+        );
+
+        let mut svg = String::new();
+        // SVG's y axis points down, so every y is flipped
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="{} {} {} {}">"#,
+            (width * scale).round(),
+            (height * scale).round(),
+            f64::from(bounds.min.x) - margin,
+            -f64::from(bounds.max.y) - margin,
+            width,
+            height
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+            f64::from(bounds.min.x) - margin,
+            -f64::from(bounds.max.y) - margin,
+            width,
+            height,
+            Colour::WHITE.hex()
+        )
+        .unwrap();
+        for path in &self.paths {
+            let points: Vec<String> = path
+                .points
+                .iter()
+                .map(|point| format!("{},{}", point.x, -i64::from(point.y)))
+                .collect();
+            writeln!(
+                svg,
+                r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="{}"/>"#,
+                points.join(" "),
+                path.colour.hex(),
+                stroke
+            )
+            .unwrap();
+        }
+        for marker in &self.markers {
+            writeln!(
+                svg,
+                r#"<circle cx="{}" cy="{}" r="{}" fill="{}"><title>{}</title></circle>"#,
+                marker.point.x,
+                -i64::from(marker.point.y),
+                radius,
+                marker.colour.hex(),
+                escape(&marker.label)
+            )
+            .unwrap();
+        }
+        svg.push_str("</svg>\n");
+        Ok(svg)
+    }
+
+    /// The scene as a binary PPM image, scaled so its longest side is at most 1000 pixels.
+    pub fn ppm(&self) -> Result<Vec<u8>, Error> {
+        let bounds = self.bounds()?;
+        let longest = bounds.width.max(bounds.height) as f64;
+        let scale = (MAX_IMAGE_SIZE as f64 / longest).min(MAX_CELL_SIZE as f64);
+        let width = (bounds.width as f64 * scale).ceil() as usize + 2 * IMAGE_MARGIN;
+        let height = (bounds.height as f64 * scale).ceil() as usize + 2 * IMAGE_MARGIN;
+        let mut pixels = vec![Colour::WHITE; width * height];
+        // The pixel at the middle of a cell
+        let pixel = |point: Point| {
+            let x = ((i64::from(point.x) - i64::from(bounds.min.x)) as f64 + 0.5) * scale;
+            let y = ((i64::from(bounds.max.y) - i64::from(point.y)) as f64 + 0.5) * scale;
+            (x as usize + IMAGE_MARGIN, y as usize + IMAGE_MARGIN)
+        };
+
+        for path in &self.paths {
+            for pair in path.points.windows(2) {
+                let ((x1, y1), (x2, y2)) = (pixel(pair[0]), pixel(pair[1]));
+                for y in y1.min(y2)..=y1.max(y2) {
+                    for x in x1.min(x2)..=x1.max(x2) {
+                        pixels[y * width + x] = path.colour;
+                    }
+                }
+            }
+        }
+        for marker in &self.markers {
+            let (x, y) = pixel(marker.point);
+            for y in y.saturating_sub(2)..(y + 3).min(height) {
+                for x in x.saturating_sub(2)..(x + 3).min(width) {
+                    pixels[y * width + x] = marker.colour;
+                }
+            }
+        }
+
+        let mut ppm = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+        for colour in pixels {
+            ppm.extend_from_slice(&[colour.red, colour.green, colour.blue]);
+        }
+        Ok(ppm)
+    }
+
+    // Fails if the scene spans more cells than fit in a usize
+    fn bounds(&self) -> Result<Bounds, Error> {
+        let mut points = self
+            .paths
+            .iter()
+            .flat_map(|path| path.points.iter())
+            .chain(self.markers.iter().map(|marker| &marker.point));
+        let first = points.next().copied().unwrap_or(Point::ORIGIN);
+        let (min, max) = points.fold((first, first), |(min, max), point| {
+            (
+                Point::new(min.x.min(point.x), min.y.min(point.y)),
+                Point::new(max.x.max(point.x), max.y.max(point.y)),
+            )
+        });
+        // Worked out in i64, as the span of two i32s can be past i32::MAX
+        let span = |low: i32, high: i32| usize::try_from(i64::from(high) - i64::from(low) + 1);
+        match (span(min.x, max.x), span(min.y, max.y)) {
+            (Ok(width), Ok(height)) => Ok(Bounds {
+                min,
+                max,
+                width,
+                height,
+            }),
+            _ => Err(Error::Custom(format!(
+                "The scene from {:?} to {:?} is too big to draw",
+                (min.x, min.y),
+                (max.x, max.y)
+            ))),
+        }
+    }
+}
+
+// Every point on the straight line between two points, including both ends
+fn line(start: Point, end: Point) -> impl Iterator<Item = Point> {
+    let (low_x, high_x) = (start.x.min(end.x), start.x.max(end.x));
+    let (low_y, high_y) = (start.y.min(end.y), start.y.max(end.y));
+    (low_x..=high_x).flat_map(move |x| (low_y..=high_y).map(move |y| Point::new(x, y)))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scene() -> Scene {
+        Scene {
+            paths: vec![Path {
+                points: vec![Point::ORIGIN, Point::new(3, 0), Point::new(3, 2)],
+                colour: Colour::RED,
+            }],
+            markers: vec![Marker {
+                point: Point::ORIGIN,
+                glyph: 'o',
+                colour: Colour::BLACK,
+                label: String::from("start"),
+            }],
+        }
+    }
+
+    #[test]
+    fn ascii_art_has_y_pointing_up() {
+        assert_eq!(
+            scene().ascii(false).unwrap(),
+            "......\n....|.\n....|.\n.o--+.\n......\no start\n"
+        );
+    }
+
+    #[test]
+    fn ascii_art_can_be_coloured() {
+        let art = scene().ascii(true).unwrap();
+        assert!(art.contains("\x1b[38;2;220;50;47m-\x1b[0m"));
+        assert!(art.starts_with("......\n"));
+    }
+
+    #[test]
+    fn the_key_lists_each_kind_of_marker_once() {
+        let mut scene = scene();
+        for x in 1..3 {
+            scene.markers.push(Marker {
+                point: Point::new(x, 0),
+                glyph: 'X',
+                colour: Colour::GREY,
+                label: String::from("crossing"),
+            });
+        }
+        assert!(scene
+            .ascii(false)
+            .unwrap()
+            .ends_with("o start\nX crossing\n"));
+    }
+
+    #[test]
+    fn big_scenes_are_drawn_as_images() {
+        let mut scene = scene();
+        assert_eq!(scene.resolve(Format::Auto).unwrap(), Format::Ascii);
+        scene.paths[0].points.push(Point::new(3, 500));
+        assert_eq!(scene.resolve(Format::Auto).unwrap(), Format::Svg);
+        assert_eq!(scene.resolve(Format::Ppm).unwrap(), Format::Ppm);
+        assert!(scene.ascii(false).is_err());
+        assert!(scene.draw(Format::Ascii, false).is_err());
+        assert!(scene.draw(Format::Auto, false).is_ok());
+    }
+
+    #[test]
+    fn svgs_flip_y() {
+        let svg = scene().svg().unwrap();
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.contains(r##"<polyline points="0,0 3,0 3,-2" fill="none" stroke="#dc322f""##));
+        assert!(svg.contains("<title>start</title>"));
+    }
+
+    #[test]
+    fn ppms_are_scaled_up_with_a_margin() {
+        let ppm = scene().ppm().unwrap();
+        // Four cells across and three down, eight pixels each
+        let header = "P6\n52 44\n255\n";
+        assert!(ppm.starts_with(header.as_bytes()));
+        assert_eq!(ppm.len(), header.len() + 52 * 44 * 3);
+    }
+
+    #[test]
+    fn scenes_as_wide_as_the_grid() {
+        let scene = Scene {
+            paths: vec![Path {
+                points: vec![Point::new(i32::MIN, 0), Point::new(i32::MAX, 0)],
+                colour: Colour::RED,
+            }],
+            markers: Vec::new(),
+        };
+        assert_eq!(scene.resolve(Format::Auto).unwrap(), Format::Svg);
+        assert!(scene.ascii(false).is_err());
+        assert!(scene
+            .svg()
+            .unwrap()
+            .contains(r#"points="-2147483648,0 2147483647,0""#));
+        let ppm = scene.ppm().unwrap();
+        assert!(ppm.starts_with(b"P6\n1020 21\n255\n"));
+
+        // A small scene in the corner of the grid, with its margin past the edge
+        let corner = Scene {
+            paths: Vec::new(),
+            markers: vec![Marker {
+                point: Point::new(i32::MIN, i32::MIN),
+                glyph: 'o',
+                colour: Colour::BLACK,
+                label: String::from("corner"),
+            }],
+        };
+        assert_eq!(corner.ascii(false).unwrap(), "...\n.o.\n...\no corner\n");
+        assert!(corner.svg().unwrap().contains(r#"cy="2147483648""#));
+    }
+
+    #[test]
+    fn formats_parse_from_their_names() {
+        assert_eq!("svg".parse(), Ok(Format::Svg));
+        assert!("png".parse::<Format>().is_err());
+    }
+}
@@ -0,0 +1,331 @@
+// Sets up a new day: a module with a parser, both parts and a test against the puzzle's example,
+// declared in its year's module and added to the year's PUZZLES, and a directory for its input in
+// the inputs directory. The first day of a new year also creates the year's module, declares it
+// in lib.rs and adds it to the registry.
+use crate::error::Error;
+use crate::input::{day_dir, day_name};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The source directory of this crate, where new days are added.
+pub const SRC_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+
+const DAY_TEMPLATE: &str = r#"use crate::error::Error;
+use crate::solution::Solution;
+
+pub struct {name};
+
+impl Solution for {name} {
+    type Input = Vec<String>;
+    type Answer = usize;
+
+    fn parse(input: &str) -> Result<Vec<String>, Error> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part_one(input: &Vec<String>) -> Result<usize, Error> {
+        Ok(input.len())
+    }
+
+    fn part_two(_input: &Vec<String>) -> Result<usize, Error> {
+        Err(Error::Custom(String::from("Unsolved")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The example from the puzzle description
+    const EXAMPLE: &str = "";
+
+    #[test]
+    fn part_one_example() -> Result<(), Error> {
+        let input = {name}::parse(EXAMPLE)?;
+        assert_eq!({name}::part_one(&input)?, 0);
+        Ok(())
+    }
+}
+"#;
+
+const YEAR_TEMPLATE: &str = "// Puzzles from Advent of Code {year}.
+use crate::registry::Puzzle;
+use crate::solution::solve;
+
+{mods}
+pub const PUZZLES: &[Puzzle] = &[
+{puzzles}];
+";
+
+// The longest line rustfmt leaves on one line
+const MAX_WIDTH: usize = 100;
+
+/// Adds a day to the crate, returning the files that were created or changed.
+pub fn new_day(
+    src_dir: &Path,
+    inputs_dir: &Path,
+    year: u32,
+    day: usize,
+) -> Result<Vec<PathBuf>, Error> {
+    let module = format!("day_{}", day_name(day)?);
+    let day_path = src_dir
+        .join(format!("year_{}", year))
+        .join(format!("{}.rs", module));
+    if day_path.exists() {
+        return Err(Error::Custom(format!(
+            "{} already exists",
+            day_path.display()
+        )));
+    }
+    let name = struct_name(&module);
+    let mut changed = Vec::new();
+
+    let year_path = src_dir.join(format!("year_{}.rs", year));
+    let mod_line = format!("pub mod {};", module);
+    let puzzle = puzzle_entry(year, day, &module, &name);
+    if year_path.exists() {
+        let source = fs::read_to_string(&year_path)?;
+        let source = add_puzzle(&add_mod(&source, &mod_line), &puzzle)?;
+        fs::write(&year_path, source)?;
+    } else {
+        let source = YEAR_TEMPLATE
+            .replace("{year}", &year.to_string())
+            .replace("{mods}", &format!("{}\n", mod_line))
+            .replace("{puzzles}", &puzzle);
+        fs::write(&year_path, source)?;
+
+        let lib_path = src_dir.join("lib.rs");
+        let lib = fs::read_to_string(&lib_path)?;
+        fs::write(&lib_path, add_mod(&lib, &format!("pub mod year_{};", year)))?;
+        changed.push(lib_path);
+
+        let registry_path = src_dir.join("registry.rs");
+        let registry = fs::read_to_string(&registry_path)?;
+        fs::write(&registry_path, add_year(&registry, year)?)?;
+        changed.push(registry_path);
+    }
+    changed.push(year_path);
+
+    fs::create_dir_all(day_path.parent().unwrap())?;
+    fs::write(&day_path, DAY_TEMPLATE.replace("{name}", &name))?;
+    changed.push(day_path);
+
+    let input_dir = day_dir(inputs_dir, year, day)?;
+    fs::create_dir_all(&input_dir)?;
+    changed.push(input_dir);
+    Ok(changed)
+}
+
+// day_twenty_one becomes DayTwentyOne
+fn struct_name(module: &str) -> String {
+    module
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+fn puzzle_entry(year: u32, day: usize, module: &str, name: &str) -> String {
+    format!(
+        "    Puzzle {{\n        year: {},\n        day: {},\n        solve: solve::<{}::{}>,\n        render: None,\n    }},\n",
+        year, day, module, name
+    )
+}
+
+// Adds a `pub mod` line to the first block of them, keeping the block sorted as rustfmt would
+fn add_mod(source: &str, mod_line: &str) -> String {
+    let mut lines: Vec<&str> = source.lines().collect();
+    let start = lines.iter().position(|line| line.starts_with("pub mod "));
+    match start {
+        Some(start) => {
+            let end = lines[start..]
+                .iter()
+                .position(|line| !line.starts_with("pub mod "))
+                .map_or(lines.len(), |length| start + length);
+            let mut mods = lines[start..end].to_vec();
+            mods.push(mod_line);
+            mods.sort_unstable();
+            lines.splice(start..end, mods);
+        }
+        None => lines.insert(0, mod_line),
+    }
+    lines.join("\n") + "\n"
+}
+
+// Adds an entry to the end of a year's PUZZLES
+fn add_puzzle(source: &str, puzzle: &str) -> Result<String, Error> {
+    let (start, end) = list_bounds(source, "pub const PUZZLES")?;
+    let existing = source[start..end].trim_matches('\n');
+    let existing = if existing.is_empty() {
+        String::new()
+    } else if existing.ends_with(',') {
+        format!("{}\n", existing)
+    } else {
+        format!("{},\n", existing)
+    };
+    Ok(format!(
+        "{}\n{}{}{}",
+        &source[..start],
+        existing,
+        puzzle,
+        &source[end..]
+    ))
+}
+
+/// Runs rustfmt over the Rust files a new day changed, apart from lib.rs. Files are left as they
+/// were if rustfmt isn't installed.
+pub fn format(paths: &[PathBuf]) -> Result<(), Error> {
+    let files: Vec<&PathBuf> = paths
+        .iter()
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("rs"))
+        .filter(|path| !path.ends_with("lib.rs"))
+        .collect();
+    match Command::new("rustfmt")
+        .args(["--edition", "2018"])
+        .args(files)
+        .status()
+    {
+        Ok(status) if !status.success() => Err(Error::Custom(String::from(
+            "rustfmt couldn't format the new day",
+        ))),
+        _ => Ok(()),
+    }
+}
+
+// Adds a year's PUZZLES to the registry's YEARS, earliest year first
+fn add_year(source: &str, year: u32) -> Result<String, Error> {
+    let (start, end) = list_bounds(source, "const YEARS")?;
+    let mut years: Vec<String> = source[start..end]
+        .split(',')
+        .map(|year| year.trim().to_string())
+        .filter(|year| !year.is_empty())
+        .collect();
+    years.push(format!("crate::year_{}::PUZZLES", year));
+    years.sort_unstable();
+
+    let line_start = source[..start].rfind('\n').map_or(0, |index| index + 1);
+    let one_line = years.join(", ");
+    let list = if start - line_start + one_line.len() + 2 <= MAX_WIDTH {
+        one_line
+    } else {
+        format!("\n    {},\n", years.join(",\n    "))
+    };
+    Ok(format!("{}{}{}", &source[..start], list, &source[end..]))
+}
+
+// Where the items of the `&[...]` list after `name` start and end
+fn list_bounds(source: &str, name: &str) -> Result<(usize, usize), Error> {
+    let missing = || Error::Custom(format!("Couldn't find {} to add to", name));
+    let declaration = source.find(name).ok_or_else(missing)?;
+    let start = declaration + source[declaration..].find("= &[").ok_or_else(missing)? + 4;
+    let end = start + source[start..].find("];").ok_or_else(missing)?;
+    Ok((start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    const LIB: &str = "pub mod answers;\npub mod year_2019;\n";
+    const REGISTRY: &str =
+        "use crate::error::Error;\n\nconst YEARS: &[&[Puzzle]] = &[crate::year_2019::PUZZLES];\n";
+
+    // A copy of the parts of the crate that new days change
+    fn crate_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("scaffold_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("src/lib.rs"), LIB).unwrap();
+        fs::write(dir.join("src/registry.rs"), REGISTRY).unwrap();
+        let year = YEAR_TEMPLATE
+            .replace("{year}", "2019")
+            .replace("{mods}", "pub mod day_one;\npub mod day_two;\n")
+            .replace(
+                "{puzzles}",
+                &(puzzle_entry(2019, 1, "day_one", "DayOne")
+                    + &puzzle_entry(2019, 2, "day_two", "DayTwo")),
+            );
+        fs::write(dir.join("src/year_2019.rs"), year).unwrap();
+        dir
+    }
+
+    #[test]
+    fn struct_names_are_camel_case() {
+        assert_eq!(struct_name("day_six"), "DaySix");
+        assert_eq!(struct_name("day_twenty_one"), "DayTwentyOne");
+    }
+
+    #[test]
+    fn adds_a_day_to_an_existing_year() {
+        let dir = crate_dir("existing_year");
+        let changed = new_day(&dir.join("src"), &dir.join("inputs"), 2019, 12).unwrap();
+        assert_eq!(changed.len(), 3);
+
+        let year = fs::read_to_string(dir.join("src/year_2019.rs")).unwrap();
+        assert!(year.contains("pub mod day_one;\npub mod day_twelve;\npub mod day_two;\n"));
+        assert!(year.ends_with(
+            "        solve: solve::<day_two::DayTwo>,\n        render: None,\n    },\n    Puzzle {\n        year: 2019,\n        day: 12,\n        solve: solve::<day_twelve::DayTwelve>,\n        render: None,\n    },\n];\n"
+        ));
+        let day = fs::read_to_string(dir.join("src/year_2019/day_twelve.rs")).unwrap();
+        assert!(day.contains("impl Solution for DayTwelve {"));
+        assert!(dir.join("inputs/2019/day_twelve").is_dir());
+        assert_eq!(fs::read_to_string(dir.join("src/lib.rs")).unwrap(), LIB);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn the_first_day_of_a_year_adds_the_year() {
+        let dir = crate_dir("new_year");
+        new_day(&dir.join("src"), &dir.join("inputs"), 2020, 1).unwrap();
+
+        let lib = fs::read_to_string(dir.join("src/lib.rs")).unwrap();
+        assert_eq!(
+            lib,
+            "pub mod answers;\npub mod year_2019;\npub mod year_2020;\n"
+        );
+        let registry = fs::read_to_string(dir.join("src/registry.rs")).unwrap();
+        assert!(registry.contains(
+            "const YEARS: &[&[Puzzle]] = &[crate::year_2019::PUZZLES, crate::year_2020::PUZZLES];\n"
+        ));
+        let year = fs::read_to_string(dir.join("src/year_2020.rs")).unwrap();
+        assert!(year.contains("pub mod day_one;\n\npub const PUZZLES: &[Puzzle] = &[\n    Puzzle {\n        year: 2020,\n        day: 1,\n"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn years_that_dont_fit_on_a_line_go_on_their_own() {
+        let mut registry = String::from(REGISTRY);
+        for year in 2015..2019 {
+            registry = add_year(&registry, year).unwrap();
+        }
+        assert!(registry.contains(
+            "const YEARS: &[&[Puzzle]] = &[\n    crate::year_2015::PUZZLES,\n    crate::year_2016::PUZZLES,\n"
+        ));
+        assert!(registry.ends_with("    crate::year_2019::PUZZLES,\n];\n"));
+        assert_eq!(
+            add_year(&registry, 2020)
+                .unwrap()
+                .matches("PUZZLES,\n")
+                .count(),
+            6
+        );
+    }
+
+    #[test]
+    fn wont_replace_an_existing_day() {
+        let dir = crate_dir("existing_day");
+        new_day(&dir.join("src"), &dir.join("inputs"), 2019, 6).unwrap();
+        assert!(new_day(&dir.join("src"), &dir.join("inputs"), 2019, 6).is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
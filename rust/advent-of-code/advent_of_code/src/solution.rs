@@ -0,0 +1,87 @@
+// Every puzzle comes in two parts that share the same input. A Solution parses the input once,
+// then solves each part from what it parsed. Parsing and each part are timed as they run.
+use crate::error::Error;
+use serde::Serialize;
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub enum Part {
+    #[serde(rename = "1")]
+    One,
+    #[serde(rename = "2")]
+    Two,
+}
+
+pub const BOTH_PARTS: [Part; 2] = [Part::One, Part::Two];
+
+pub trait Solution {
+    type Input;
+    type Answer: fmt::Display;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>;
+    fn part_one(input: &Self::Input) -> Result<Self::Answer, Error>;
+    fn part_two(input: &Self::Input) -> Result<Self::Answer, Error>;
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Answer {
+    pub part: Part,
+    pub answer: String,
+    /// How long the part took to solve, not counting parsing.
+    pub time: Duration,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Solved {
+    pub parse_time: Duration,
+    pub answers: Vec<Answer>,
+}
+
+/// Parses the input and solves the given parts of the puzzle from it.
+pub fn solve<S: Solution>(input: &str, parts: &[Part]) -> Result<Solved, Error> {
+    let start = Instant::now();
+    let input = S::parse(input)?;
+    let parse_time = start.elapsed();
+    let answers = parts
+        .iter()
+        .map(|part| {
+            let start = Instant::now();
+            let answer = match part {
+                Part::One => S::part_one(&input)?.to_string(),
+                Part::Two => S::part_two(&input)?.to_string(),
+            };
+            Ok(Answer {
+                part: *part,
+                answer,
+                time: start.elapsed(),
+            })
+        })
+        .collect::<Result<_, Error>>()?;
+    Ok(Solved {
+        parse_time,
+        answers,
+    })
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" | "one" => Ok(Part::One),
+            "2" | "two" => Ok(Part::Two),
+            _ => Err(format!("{} isn't a part, use 1 or 2", s)),
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
@@ -0,0 +1,265 @@
+// Reports how long each puzzle takes, either from a single run or as a benchmark that solves it
+// repeatedly after some warmup runs. Reports serialize to JSON with times in nanoseconds so they
+// can be kept and compared between commits.
+use crate::answers::{Answers, Verdict};
+use crate::error::Error;
+use crate::registry::Puzzle;
+use crate::solution::{Part, Solved};
+use serde::{Serialize, Serializer};
+use std::fmt;
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Report {
+    pub year: u32,
+    pub day: usize,
+    #[serde(rename = "parse_ns", serialize_with = "nanoseconds")]
+    pub parse_time: Duration,
+    pub parts: Vec<PartReport>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PartReport {
+    pub part: Part,
+    pub answer: String,
+    #[serde(rename = "time_ns", serialize_with = "nanoseconds")]
+    pub time: Duration,
+    #[serde(flatten)]
+    pub verdict: Verdict,
+}
+
+impl Report {
+    pub fn new(puzzle: &Puzzle, solved: Solved) -> Self {
+        Report {
+            year: puzzle.year,
+            day: puzzle.day,
+            parse_time: solved.parse_time,
+            parts: solved
+                .answers
+                .into_iter()
+                .map(|answer| PartReport {
+                    part: answer.part,
+                    answer: answer.answer,
+                    time: answer.time,
+                    verdict: Verdict::Unknown,
+                })
+                .collect(),
+        }
+    }
+
+    /// Checks each part's answer against the recorded answers.
+    pub fn check(&mut self, answers: &Answers) {
+        for part in &mut self.parts {
+            part.verdict = answers.check(self.year, self.day, part.part, &part.answer);
+        }
+    }
+
+    pub fn incorrect(&self) -> usize {
+        self.parts
+            .iter()
+            .filter(|part| matches!(part.verdict, Verdict::Incorrect { .. }))
+            .count()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct Stats {
+    #[serde(rename = "min_ns", serialize_with = "nanoseconds")]
+    pub min: Duration,
+    #[serde(rename = "median_ns", serialize_with = "nanoseconds")]
+    pub median: Duration,
+    #[serde(rename = "max_ns", serialize_with = "nanoseconds")]
+    pub max: Duration,
+}
+
+impl Stats {
+    /// The fastest, median and slowest of some times, or None if there aren't any.
+    // `is_multiple_of` needs a newer Rust than the crate supports
+    #[allow(unknown_lints, clippy::manual_is_multiple_of)]
+    pub fn new(times: &[Duration]) -> Option<Self> {
+        if times.is_empty() {
+            return None;
+        }
+        let mut times = times.to_vec();
+        times.sort_unstable();
+        let middle = times.len() / 2;
+        let median = if times.len() % 2 == 0 {
+            (times[middle - 1] + times[middle]) / 2
+        } else {
+            times[middle]
+        };
+        Some(Stats {
+            min: times[0],
+            median,
+            max: times[times.len() - 1],
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Benchmark {
+    pub year: u32,
+    pub day: usize,
+    pub warmup: usize,
+    pub runs: usize,
+    pub parse: Stats,
+    pub parts: Vec<PartStats>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PartStats {
+    pub part: Part,
+    pub answer: String,
+    #[serde(flatten)]
+    pub stats: Stats,
+}
+
+/// Solves the puzzle `warmup` times without timing it, then `runs` more times to time it.
+pub fn bench(
+    puzzle: &Puzzle,
+    input: &str,
+    parts: &[Part],
+    warmup: usize,
+    runs: usize,
+) -> Result<Benchmark, Error> {
+    if runs == 0 {
+        return Err(Error::Custom(String::from(
+            "A benchmark needs at least one run",
+        )));
+    }
+    for _ in 0..warmup {
+        (puzzle.solve)(input, parts)?;
+    }
+
+    let mut parse_times = Vec::with_capacity(runs);
+    let mut part_times = vec![Vec::with_capacity(runs); parts.len()];
+    let mut answers = Vec::new();
+    for _ in 0..runs {
+        let solved = (puzzle.solve)(input, parts)?;
+        parse_times.push(solved.parse_time);
+        for (times, answer) in part_times.iter_mut().zip(&solved.answers) {
+            times.push(answer.time);
+        }
+        answers = solved.answers;
+    }
+
+    Ok(Benchmark {
+        year: puzzle.year,
+        day: puzzle.day,
+        warmup,
+        runs,
+        // There's at least one run, so there's always a time to take stats from
+        parse: Stats::new(&parse_times).unwrap(),
+        parts: answers
+            .into_iter()
+            .zip(&part_times)
+            .map(|(answer, times)| PartStats {
+                part: answer.part,
+                answer: answer.answer,
+                stats: Stats::new(times).unwrap(),
+            })
+            .collect(),
+    })
+}
+
+fn nanoseconds<S: Serializer>(time: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(time.as_nanos() as u64)
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "min {:?}, median {:?}, max {:?}",
+            self.min, self.median, self.max
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{solve, Solution, BOTH_PARTS};
+
+    struct Doubling;
+
+    impl Solution for Doubling {
+        type Input = i32;
+        type Answer = i32;
+
+        fn parse(input: &str) -> Result<i32, Error> {
+            input
+                .trim()
+                .parse()
+                .map_err(|_| Error::Custom(format!("{} isn't a number", input)))
+        }
+
+        fn part_one(input: &i32) -> Result<i32, Error> {
+            Ok(input * 2)
+        }
+
+        fn part_two(input: &i32) -> Result<i32, Error> {
+            Ok(input * 4)
+        }
+    }
+
+    const DOUBLING: Puzzle = Puzzle {
+        year: 2019,
+        day: 1,
+        solve: solve::<Doubling>,
+        render: None,
+    };
+
+    fn millis(times: &[u64]) -> Vec<Duration> {
+        times
+            .iter()
+            .map(|&time| Duration::from_millis(time))
+            .collect()
+    }
+
+    #[test]
+    fn stats_of_an_odd_number_of_times() {
+        let stats = Stats::new(&millis(&[5, 1, 3])).unwrap();
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.max, Duration::from_millis(5));
+    }
+
+    #[test]
+    fn median_of_an_even_number_of_times_is_the_middle_two_averaged() {
+        let stats = Stats::new(&millis(&[8, 2, 4, 6])).unwrap();
+        assert_eq!(stats.median, Duration::from_millis(5));
+    }
+
+    #[test]
+    fn no_stats_without_times() {
+        assert_eq!(Stats::new(&[]), None);
+    }
+
+    #[test]
+    fn bench_keeps_the_answers() {
+        let benchmark = bench(&DOUBLING, "21", &BOTH_PARTS, 1, 3).unwrap();
+        assert_eq!(benchmark.runs, 3);
+        let answers: Vec<&str> = benchmark
+            .parts
+            .iter()
+            .map(|part| part.answer.as_str())
+            .collect();
+        assert_eq!(answers, vec!["42", "84"]);
+    }
+
+    #[test]
+    fn bench_needs_a_run() {
+        assert!(bench(&DOUBLING, "21", &BOTH_PARTS, 0, 0).is_err());
+    }
+
+    #[test]
+    fn reports_serialize_times_in_nanoseconds() {
+        let report = Report::new(&DOUBLING, solve::<Doubling>("21", &[Part::Two]).unwrap());
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["parts"][0]["part"], "2");
+        assert_eq!(json["parts"][0]["answer"], "84");
+        assert!(json["parse_ns"].is_u64());
+        assert!(json["parts"][0]["time_ns"].is_u64());
+    }
+}
@@ -0,0 +1,43 @@
+// Puzzles from Advent of Code 2019.
+use crate::registry::Puzzle;
+use crate::render;
+use crate::solution::solve;
+
+pub mod day_five;
+pub mod day_four;
+pub mod day_one;
+pub mod day_three;
+pub mod day_two;
+
+pub const PUZZLES: &[Puzzle] = &[
+    Puzzle {
+        year: 2019,
+        day: 1,
+        solve: solve::<day_one::DayOne>,
+        render: None,
+    },
+    Puzzle {
+        year: 2019,
+        day: 2,
+        solve: solve::<day_two::DayTwo>,
+        render: None,
+    },
+    Puzzle {
+        year: 2019,
+        day: 3,
+        solve: solve::<day_three::DayThree>,
+        render: Some(render::scene::<day_three::DayThree>),
+    },
+    Puzzle {
+        year: 2019,
+        day: 4,
+        solve: solve::<day_four::DayFour>,
+        render: None,
+    },
+    Puzzle {
+        year: 2019,
+        day: 5,
+        solve: solve::<day_five::DayFive>,
+        render: None,
+    },
+];
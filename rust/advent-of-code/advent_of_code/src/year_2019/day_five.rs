@@ -32,24 +32,40 @@
 // - Tests and integration tests
 // - Doc tests
 
-use crate::computer::{Computer, ComputerActions};
+use crate::computer::{parse_program, Computer, ComputerActions};
 use crate::error::Error;
-use std::fs;
+use crate::solution::Solution;
 
-const FILENAME1: &str = "./inputs/day_five/input.txt";
+// System IDs for the air conditioner unit (part one) and the thermal radiator controller (part two)
+const AIR_CONDITIONER_ID: i64 = 1;
+const THERMAL_RADIATOR_ID: i64 = 5;
 
-pub fn run() -> Result<Vec<i32>, Error> {
-    let mut computer = Computer::new(0, 0, get_file_input(FILENAME1));
-    computer.run();
-    let output_diagnostic = computer.outputs[computer.outputs.len() - 1];
-    Ok(vec![output_diagnostic])
+pub struct DayFive;
+
+impl Solution for DayFive {
+    type Input = Vec<i64>;
+    type Answer = i64;
+
+    fn parse(input: &str) -> Result<Vec<i64>, Error> {
+        parse_program(input)
+    }
+
+    fn part_one(program: &Vec<i64>) -> Result<i64, Error> {
+        run_diagnostic(program.clone(), AIR_CONDITIONER_ID)
+    }
+
+    fn part_two(program: &Vec<i64>) -> Result<i64, Error> {
+        run_diagnostic(program.clone(), THERMAL_RADIATOR_ID)
+    }
 }
 
-fn get_file_input(filename: &str) -> Vec<i32> {
-    fs::read_to_string(filename)
-        .expect("Something went wrong reading the file")
-        .trim_end()
-        .split(",")
-        .map(|v| v.to_string().parse::<i32>().unwrap())
-        .collect()
+fn run_diagnostic(program: Vec<i64>, system_id: i64) -> Result<i64, Error> {
+    let mut computer = Computer::new(0, 0, program);
+    computer.push_input(system_id);
+    computer.run_to_halt()?;
+    computer
+        .outputs
+        .last()
+        .copied()
+        .ok_or_else(|| Error::Custom(String::from("Diagnostic program produced no output")))
 }
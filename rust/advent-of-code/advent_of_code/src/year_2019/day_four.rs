@@ -0,0 +1,300 @@
+// Given an input, identify a value that fulfils the following criteria;
+// 1. It is a number with up to twenty digits (the puzzle's are six, but any u64 range works).
+// 2. The value is within the range given in your puzzle input.
+// 3. Two adjacent digits are the same (like 22 in 122345).
+// 4. Going from left to right, the digits never decrease; they only ever increase or stay the same (like 111123 or 135679).
+
+// Approach:
+// Only numbers whose digits never decrease can be passwords, and there are far fewer of them than
+// numbers in the range, so build just those a digit at a time, never going outside the range
+// Because the digits never decrease, equal digits are always next to each other, so a number can
+// be checked against the rules from the lengths of its runs of equal digits
+// Each rule looks at the runs, so the same search counts the passwords for both parts
+
+// Structure:
+// 1. The range is split by how many digits the numbers in it have, so any length of number works
+// 2. For each length, the digits are chosen left to right, each no smaller than the last and
+//    staying within the range while the digits so far are the same as the range's ends
+// 3. The runs of equal digits are kept track of as the digits are chosen
+// 4. A finished number is counted if its runs pass every rule
+
+use crate::error::Error;
+use crate::parse::{self, Input};
+use crate::solution::Solution;
+
+pub struct DayFour;
+
+impl Solution for DayFour {
+    type Input = (u64, u64);
+    type Answer = usize;
+
+    fn parse(input: &str) -> Result<(u64, u64), Error> {
+        parse_range(input)
+    }
+
+    fn part_one(&(low, high): &(u64, u64)) -> Result<usize, Error> {
+        Ok(count_passwords(low, high, &[&AdjacentPair]))
+    }
+
+    // Larger groups of equal digits no longer count as a pair
+    fn part_two(&(low, high): &(u64, u64)) -> Result<usize, Error> {
+        Ok(count_passwords(low, high, &[&ExactPair]))
+    }
+}
+
+// The input is the range as two numbers separated by a dash, e.g. 234208-765869
+fn parse_range(input: &str) -> Result<(u64, u64), Error> {
+    Ok(parse::pair(Input::new(input), '-')?)
+}
+
+/// Something a password has to satisfy, given the lengths of its runs of equal digits in order,
+/// e.g. [2, 1, 3] for 223444.
+pub trait Rule {
+    fn allows(&self, runs: &[usize]) -> bool;
+}
+
+impl<F: Fn(&[usize]) -> bool> Rule for F {
+    fn allows(&self, runs: &[usize]) -> bool {
+        self(runs)
+    }
+}
+
+/// At least two adjacent digits are the same.
+pub struct AdjacentPair;
+
+impl Rule for AdjacentPair {
+    fn allows(&self, runs: &[usize]) -> bool {
+        runs.iter().any(|&run| run >= 2)
+    }
+}
+
+/// Some digit appears exactly twice in a row.
+pub struct ExactPair;
+
+impl Rule for ExactPair {
+    fn allows(&self, runs: &[usize]) -> bool {
+        runs.contains(&2)
+    }
+}
+
+/// No digit appears three or more times in a row.
+pub struct NoTriple;
+
+impl Rule for NoTriple {
+    fn allows(&self, runs: &[usize]) -> bool {
+        runs.iter().all(|&run| run < 3)
+    }
+}
+
+/// Counts the numbers from `low` to `high` whose digits never decrease and that pass every rule.
+///
+/// # Examples
+///
+/// ```
+/// use advent_of_code::year_2019::day_four::*;
+///
+/// assert_eq!(count_passwords(111110, 111125, &[&AdjacentPair]), 13);
+/// assert_eq!(count_passwords(112233, 112234, &[&ExactPair, &NoTriple]), 2);
+/// ```
+pub fn count_passwords(low: u64, high: u64, rules: &[&dyn Rule]) -> usize {
+    let mut count = 0;
+    non_decreasing(low, high, |_, runs| {
+        if rules.iter().all(|rule| rule.allows(runs)) {
+            count += 1;
+        }
+    });
+    count
+}
+
+/// Calls `visit` with the digits of each number from `low` to `high` whose digits never
+/// decrease, in increasing order, along with the lengths of its runs of equal digits.
+pub fn non_decreasing(low: u64, high: u64, mut visit: impl FnMut(&[u8], &[usize])) {
+    for length in digit_count(low)..=digit_count(high) {
+        // The part of the range with this many digits
+        let shortest = if length == 1 {
+            0
+        } else {
+            10_u64.pow(length - 1)
+        };
+        // Every 20 digit number is past u64::MAX
+        let longest = 10_u64
+            .checked_pow(length)
+            .map_or(u64::MAX, |power| power - 1);
+        let (from, to) = (low.max(shortest), high.min(longest));
+        if from > to {
+            continue;
+        }
+        let mut search = Search {
+            low: digits(from),
+            high: digits(to),
+            digits: Vec::with_capacity(length as usize),
+            runs: Vec::new(),
+        };
+        search.choose(true, true, &mut visit);
+    }
+}
+
+// The digits chosen so far for numbers between two of the same length
+struct Search {
+    low: Vec<u8>,
+    high: Vec<u8>,
+    digits: Vec<u8>,
+    runs: Vec<usize>,
+}
+
+impl Search {
+    // While `at_low` or `at_high`, the digits so far are the same as the start of that end of the
+    // range, so the next digit can't go past that end's
+    fn choose(&mut self, at_low: bool, at_high: bool, visit: &mut impl FnMut(&[u8], &[usize])) {
+        let position = self.digits.len();
+        if position == self.low.len() {
+            visit(&self.digits, &self.runs);
+            return;
+        }
+        let last = self.digits.last().copied().unwrap_or(0);
+        let smallest = if at_low {
+            last.max(self.low[position])
+        } else {
+            last
+        };
+        let largest = if at_high { self.high[position] } else { 9 };
+        for digit in smallest..=largest {
+            let repeats = position > 0 && digit == last;
+            if repeats {
+                *self.runs.last_mut().unwrap() += 1;
+            } else {
+                self.runs.push(1);
+            }
+            self.digits.push(digit);
+            self.choose(
+                at_low && digit == self.low[position],
+                at_high && digit == self.high[position],
+                visit,
+            );
+            self.digits.pop();
+            if repeats {
+                *self.runs.last_mut().unwrap() -= 1;
+            } else {
+                self.runs.pop();
+            }
+        }
+    }
+}
+
+fn digit_count(number: u64) -> u32 {
+    number.checked_ilog10().unwrap_or(0) + 1
+}
+
+fn digits(number: u64) -> Vec<u8> {
+    number
+        .to_string()
+        .bytes()
+        .map(|digit| digit - b'0')
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Checks every number in the range the slow way
+    fn brute_force(low: u64, high: u64, rules: &[&dyn Rule]) -> usize {
+        (low..=high)
+            .filter(|number| {
+                let digits = digits(*number);
+                if digits.windows(2).any(|pair| pair[0] > pair[1]) {
+                    return false;
+                }
+                let mut runs: Vec<usize> = Vec::new();
+                for (i, digit) in digits.iter().enumerate() {
+                    if i > 0 && digits[i - 1] == *digit {
+                        *runs.last_mut().unwrap() += 1;
+                    } else {
+                        runs.push(1);
+                    }
+                }
+                rules.iter().all(|rule| rule.allows(&runs))
+            })
+            .count()
+    }
+
+    #[test]
+    fn puzzle_examples() {
+        let passes = |number, rule: &dyn Rule| count_passwords(number, number, &[rule]) == 1;
+        assert!(passes(111111, &AdjacentPair));
+        assert!(!passes(223450, &AdjacentPair));
+        assert!(!passes(123789, &AdjacentPair));
+        assert!(passes(112233, &ExactPair));
+        assert!(!passes(123444, &ExactPair));
+        assert!(passes(111122, &ExactPair));
+    }
+
+    #[test]
+    fn matches_checking_every_number() {
+        let rules: [&[&dyn Rule]; 4] = [&[], &[&AdjacentPair], &[&ExactPair], &[&NoTriple]];
+        for rules in rules.iter() {
+            for &(low, high) in &[(0, 999), (5, 12_345), (234_208, 265_869), (889, 1_000)] {
+                assert_eq!(
+                    count_passwords(low, high, rules),
+                    brute_force(low, high, rules)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn numbers_are_visited_in_order() {
+        let mut numbers = Vec::new();
+        non_decreasing(7, 23, |digits, runs| {
+            numbers.push((digits.to_vec(), runs.to_vec()));
+        });
+        assert_eq!(
+            numbers,
+            vec![
+                (vec![7], vec![1]),
+                (vec![8], vec![1]),
+                (vec![9], vec![1]),
+                (vec![1, 1], vec![2]),
+                (vec![1, 2], vec![1, 1]),
+                (vec![1, 3], vec![1, 1]),
+                (vec![1, 4], vec![1, 1]),
+                (vec![1, 5], vec![1, 1]),
+                (vec![1, 6], vec![1, 1]),
+                (vec![1, 7], vec![1, 1]),
+                (vec![1, 8], vec![1, 1]),
+                (vec![1, 9], vec![1, 1]),
+                (vec![2, 2], vec![2]),
+                (vec![2, 3], vec![1, 1]),
+            ]
+        );
+    }
+
+    #[test]
+    fn rules_can_be_closures() {
+        let four_of_a_kind = |runs: &[usize]| runs.contains(&4);
+        // Nine with four digits, and 72 with a fifth digit before or after the four
+        assert_eq!(count_passwords(0, 99_999, &[&four_of_a_kind]), 81);
+    }
+
+    #[test]
+    fn large_ranges() {
+        // Every number with up to twelve digits that never decrease, apart from zero
+        let count = count_passwords(1, 999_999_999_999, &[]);
+        assert_eq!(
+            count,
+            (1..=12).map(|length| choose(length + 8, 8)).sum::<usize>()
+        );
+    }
+
+    #[test]
+    fn ranges_up_to_the_largest_u64() {
+        assert_eq!(count_passwords(u64::MAX - 1000, u64::MAX, &[]), 0);
+        // 17 followed by eighteen 9s is the only one of the twenty digit numbers from it on
+        let low = 17_999_999_999_999_999_999;
+        assert_eq!(count_passwords(low, u64::MAX, &[]), 1);
+    }
+
+    fn choose(n: usize, k: usize) -> usize {
+        (0..k).fold(1, |result, i| result * (n - i) / (i + 1))
+    }
+}
@@ -0,0 +1,52 @@
+use crate::error::Error;
+use crate::parse::{self, Input};
+use crate::solution::Solution;
+use math::round::floor;
+
+pub struct DayOne;
+
+impl Solution for DayOne {
+    type Input = Vec<i32>;
+    type Answer = i32;
+
+    fn parse(input: &str) -> Result<Vec<i32>, Error> {
+        parse_input(input)
+    }
+
+    fn part_one(masses: &Vec<i32>) -> Result<i32, Error> {
+        Ok(total_fuel_requirement(masses))
+    }
+
+    fn part_two(masses: &Vec<i32>) -> Result<i32, Error> {
+        Ok(calculate_additional_total_fuel(masses))
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<i32>, Error> {
+    Ok(parse::lines(Input::new(input), |line| line.parse())?)
+}
+
+fn total_fuel_requirement(masses: &[i32]) -> i32 {
+    masses
+        .iter()
+        .fold(0, |acc, x| acc + (floor(*x as f64 / 3.0, 0) as i32 - 2))
+}
+
+fn calculate_additional_total_fuel(masses: &[i32]) -> i32 {
+    let mut total: i32 = 0;
+    for &mass in masses {
+        let mut total_additional_fuel = 0;
+        sum_additional_fuel(mass, &mut total_additional_fuel);
+        total += total_additional_fuel;
+    }
+
+    total
+}
+
+fn sum_additional_fuel(mass: i32, total_fuel_required: &mut i32) {
+    let mass_calc = floor(mass as f64 / 3.0, 0) as i32 - 2;
+    if mass_calc > 0 {
+        *total_fuel_required += mass_calc;
+        sum_additional_fuel(mass_calc, total_fuel_required);
+    }
+}
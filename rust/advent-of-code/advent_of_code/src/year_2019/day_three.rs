@@ -0,0 +1,212 @@
+// Two wires are connected centrally and extend outward
+// Trace the path each wire takes as a series of segments from the central port
+// Aim is to find the point that both wires cross closest to the central port
+use crate::error::{Error, ParseError};
+use crate::geometry::{Crossing, Direction, Point, Wire};
+use crate::parse::{self, Input};
+use crate::render::{Colour, Marker, Path, Render, Scene};
+use crate::solution::Solution;
+
+pub struct DayThree;
+
+pub struct Wires {
+    first: Wire,
+    second: Wire,
+}
+
+impl Solution for DayThree {
+    type Input = Wires;
+    type Answer = usize;
+
+    fn parse(input: &str) -> Result<Wires, Error> {
+        let mut wires = parse::lines(Input::new(input), |line| {
+            let line = line.trim();
+            let moves = parse::separated(line, ',', parse_move)?;
+            Wire::new(Point::ORIGIN, &moves)
+                .ok_or_else(|| line.error("The wire goes past the edge of the grid"))
+        })?;
+        if wires.len() != 2 {
+            return Err(Error::Custom(format!(
+                "Expected two wires, found {}",
+                wires.len()
+            )));
+        }
+        let second = wires.pop().unwrap();
+        let first = wires.pop().unwrap();
+        Ok(Wires { first, second })
+    }
+
+    // The distance from the central port to the closest crossing
+    fn part_one(wires: &Wires) -> Result<usize, Error> {
+        crossings(wires)
+            .iter()
+            .map(|crossing| crossing.point.manhattan_distance(Point::ORIGIN) as usize)
+            .min()
+            .ok_or_else(no_crossings)
+    }
+
+    // The fewest combined steps the wires take to reach a crossing
+    fn part_two(wires: &Wires) -> Result<usize, Error> {
+        crossings(wires)
+            .iter()
+            .map(|crossing| crossing.steps)
+            .min()
+            .ok_or_else(no_crossings)
+    }
+}
+
+// Both wires, with every crossing marked and the ones each part picks out highlighted
+impl Render for DayThree {
+    fn render(wires: &Wires) -> Result<Scene, Error> {
+        let mut crossings = crossings(wires);
+        crossings.sort_by_key(|crossing| crossing.point);
+        let mut markers: Vec<Marker> = crossings
+            .iter()
+            .map(|crossing| marker(crossing.point, 'X', Colour::GREY, "crossing"))
+            .collect();
+        markers.push(marker(Point::ORIGIN, 'o', Colour::BLACK, "central port"));
+        let closest = crossings
+            .iter()
+            .min_by_key(|crossing| crossing.point.manhattan_distance(Point::ORIGIN));
+        if let Some(closest) = closest {
+            let label = format!(
+                "closest crossing {:?}, distance {}",
+                (closest.point.x, closest.point.y),
+                closest.point.manhattan_distance(Point::ORIGIN)
+            );
+            markers.push(marker(closest.point, 'C', Colour::GREEN, &label));
+        }
+        if let Some(fewest) = crossings.iter().min_by_key(|crossing| crossing.steps) {
+            let label = format!(
+                "fewest steps crossing {:?}, {} steps",
+                (fewest.point.x, fewest.point.y),
+                fewest.steps
+            );
+            markers.push(marker(fewest.point, 'S', Colour::MAGENTA, &label));
+        }
+        Ok(Scene {
+            paths: vec![
+                path(&wires.first, Colour::RED),
+                path(&wires.second, Colour::BLUE),
+            ],
+            markers,
+        })
+    }
+}
+
+fn path(wire: &Wire, colour: Colour) -> Path {
+    let start = wire.segments().first().map(|segment| segment.start);
+    Path {
+        points: start
+            .into_iter()
+            .chain(wire.segments().iter().map(|segment| segment.end))
+            .collect(),
+        colour,
+    }
+}
+
+fn marker(point: Point, glyph: char, colour: Colour, label: &str) -> Marker {
+    Marker {
+        point,
+        glyph,
+        colour,
+        label: String::from(label),
+    }
+}
+
+fn crossings(wires: &Wires) -> Vec<Crossing> {
+    wires.first.crossings(&wires.second)
+}
+
+fn no_crossings() -> Error {
+    Error::Custom(String::from("The wires never cross"))
+}
+
+// A move is a direction and a distance, e.g. R75
+fn parse_move(input: Input) -> Result<(Direction, u32), ParseError> {
+    let (direction, distance) = input.trim().split_at(1)?;
+    Ok((direction.parse()?, distance.parse()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLES: [(&str, usize, usize); 3] = [
+        ("R8,U5,L5,D3\nU7,R6,D4,L4\n", 6, 30),
+        (
+            "R75,D30,R83,U83,L12,D49,R71,U7,L72\nU62,R66,U55,R34,D71,R55,D58,R83\n",
+            159,
+            610,
+        ),
+        (
+            "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51\nU98,R91,D20,R16,D67,R40,U7,R15,U6,R7\n",
+            135,
+            410,
+        ),
+    ];
+
+    #[test]
+    fn examples() -> Result<(), Error> {
+        for (input, distance, steps) in EXAMPLES.iter() {
+            let wires = DayThree::parse(input)?;
+            assert_eq!(DayThree::part_one(&wires)?, *distance);
+            assert_eq!(DayThree::part_two(&wires)?, *steps);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn drawing_the_first_example() -> Result<(), Error> {
+        let wires = DayThree::parse(EXAMPLES[0].0)?;
+        assert_eq!(
+            DayThree::render(&wires)?.ascii(false)?,
+            "...........\n\
+             .+-----+...\n\
+             .|.....|...\n\
+             .|..+--S-+.\n\
+             .|..|..|.|.\n\
+             .|.-C--+.|.\n\
+             .|..|....|.\n\
+             .|.......|.\n\
+             .o-------+.\n\
+             ...........\n\
+             X crossing\n\
+             o central port\n\
+             C closest crossing (3, 3), distance 6\n\
+             S fewest steps crossing (6, 5), 30 steps\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn wires_that_never_cross() -> Result<(), Error> {
+        let wires = DayThree::parse("R8\nL8\n")?;
+        assert!(DayThree::part_one(&wires).is_err());
+        let wires = DayThree::parse("R2147483647\nL2147483647\n")?;
+        assert!(DayThree::part_one(&wires).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn long_wires() -> Result<(), Error> {
+        let wires = DayThree::parse("R2147483647,U5\nU3,R2147483647\n")?;
+        assert_eq!(DayThree::part_one(&wires)?, 2147483650);
+        assert_eq!(DayThree::part_two(&wires)?, 2 * 2147483650);
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_negative_distances_and_wires_off_the_grid() {
+        let error = DayThree::parse("R8,L-3\nU7\n").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "\"-3\" isn't a valid u32 at line 1, column 5"
+        );
+        let error = DayThree::parse("U1\nR2147483647,R1\n").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "The wire goes past the edge of the grid at line 2, column 1"
+        );
+    }
+}
@@ -0,0 +1,37 @@
+use crate::computer::symbolic::solve_noun_verb;
+use crate::computer::{parse_program, Computer, ComputerActions};
+use crate::error::Error;
+use crate::solution::Solution;
+
+const TARGET: i64 = 19_690_720;
+
+pub struct DayTwo;
+
+impl Solution for DayTwo {
+    type Input = Vec<i64>;
+    type Answer = i64;
+
+    fn parse(input: &str) -> Result<Vec<i64>, Error> {
+        parse_program(input)
+    }
+
+    // Put the program back into the "1202 program alarm" state it had before it caught fire
+    fn part_one(program: &Vec<i64>) -> Result<i64, Error> {
+        let mut computer = Computer::new(12, 2, program.clone());
+        computer.restore_gravity_assist_program();
+        computer.run_to_halt()?;
+        Ok(computer.memory()[0])
+    }
+
+    fn part_two(program: &Vec<i64>) -> Result<i64, Error> {
+        let mut computer = Computer::new(0, 0, program.clone());
+        computer.enable_instruction_cache();
+        let (noun, verb) = solve_noun_verb(&computer, 0..100, 0..100, TARGET)
+            .first()
+            .copied()
+            .ok_or_else(|| Error::Custom(format!("No noun and verb give {}", TARGET)))?;
+        computer.reset(noun, verb);
+        computer.run_to_halt()?;
+        Ok(computer.memory()[0])
+    }
+}
@@ -0,0 +1,28 @@
+// Solves every puzzle in the registry from its input and checks it against the answers file, so
+// a new day is covered as soon as it's registered and its answers are recorded.
+use advent_of_code::answers::{self, Answers, Verdict};
+use advent_of_code::error::Error;
+use advent_of_code::input::DEFAULT_INPUTS_DIR;
+use advent_of_code::registry;
+use advent_of_code::solution::BOTH_PARTS;
+use std::path::Path;
+
+#[test]
+fn every_registered_day_gives_its_recorded_answers() -> Result<(), Error> {
+    let inputs_dir = Path::new(DEFAULT_INPUTS_DIR);
+    let answers = Answers::load(&answers::default_path(inputs_dir))?;
+
+    let mut failures = Vec::new();
+    for puzzle in registry::puzzles() {
+        for (answer, verdict) in answers::verify(puzzle, inputs_dir, &answers, &BOTH_PARTS)? {
+            if verdict != Verdict::Correct {
+                failures.push(format!(
+                    "{} day {} part {}: got {}, {}",
+                    puzzle.year, puzzle.day, answer.part, answer.answer, verdict
+                ));
+            }
+        }
+    }
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+    Ok(())
+}
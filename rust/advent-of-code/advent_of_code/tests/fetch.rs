@@ -0,0 +1,177 @@
+// Fetches inputs from a stand-in for the Advent of Code site, served from a local port, so the
+// fetching can be tested without a network or a real session token.
+use advent_of_code::fetch::{Fetched, Fetcher};
+use advent_of_code::input::default_path;
+use std::env;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const SESSION: &str = "secret";
+const INPUT: &str = "12\n14\n1969\n";
+
+#[derive(Clone, Debug)]
+struct Request {
+    path: String,
+    cookie: Option<String>,
+    user_agent: Option<String>,
+    received: Instant,
+}
+
+struct MockServer {
+    url: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl MockServer {
+    // Only 2019 day 1 is available, and only to the SESSION token
+    fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&requests);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                respond(stream.unwrap(), &log);
+            }
+        });
+        MockServer { url, requests }
+    }
+
+    fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    fn fetcher(&self, session: &str, inputs_dir: &Path) -> Fetcher {
+        let mut fetcher = Fetcher::new(String::from(session), inputs_dir);
+        fetcher.set_base_url(&self.url);
+        fetcher.set_min_interval(Duration::from_millis(0));
+        fetcher
+    }
+}
+
+fn respond(mut stream: TcpStream, log: &Mutex<Vec<Request>>) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut line = String::new();
+    reader.read_line(&mut line).unwrap();
+    let path = line.split_whitespace().nth(1).unwrap_or("").to_string();
+    let mut request = Request {
+        path,
+        cookie: None,
+        user_agent: None,
+        received: Instant::now(),
+    };
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header).unwrap();
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            match name.to_ascii_lowercase().as_str() {
+                "cookie" => request.cookie = Some(value.trim().to_string()),
+                "user-agent" => request.user_agent = Some(value.trim().to_string()),
+                _ => {}
+            }
+        }
+    }
+
+    let (status, body) = if request.cookie.as_deref() != Some("session=secret") {
+        (
+            "400 Bad Request",
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+        )
+    } else if request.path == "/2019/day/1/input" {
+        ("200 OK", INPUT)
+    } else {
+        (
+            "404 Not Found",
+            "Please don't repeatedly request this endpoint before it unlocks!\n",
+        )
+    };
+    log.lock().unwrap().push(request);
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )
+    .unwrap();
+}
+
+fn inputs_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("fetch_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn inputs_are_downloaded_once() {
+    let server = MockServer::start();
+    let dir = inputs_dir("once");
+    let fetcher = server.fetcher(SESSION, &dir);
+    let path = default_path(&dir, 2019, 1).unwrap();
+
+    assert_eq!(
+        fetcher.fetch(2019, 1).unwrap(),
+        Fetched::Downloaded(path.clone())
+    );
+    assert_eq!(fs::read_to_string(&path).unwrap(), INPUT);
+    assert_eq!(fetcher.fetch(2019, 1).unwrap(), Fetched::Cached(path));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path, "/2019/day/1/input");
+    assert_eq!(requests[0].cookie.as_deref(), Some("session=secret"));
+    assert!(requests[0]
+        .user_agent
+        .as_deref()
+        .unwrap()
+        .starts_with("advent_of_code/"));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn a_rejected_session_leaves_no_input() {
+    let server = MockServer::start();
+    let dir = inputs_dir("rejected");
+    let error = server.fetcher("wrong", &dir).fetch(2019, 1).unwrap_err();
+
+    assert!(error.to_string().contains("rejected the session token"));
+    assert!(!default_path(&dir, 2019, 1).unwrap().exists());
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn locked_days_are_reported() {
+    let server = MockServer::start();
+    let dir = inputs_dir("locked");
+    let error = server.fetcher(SESSION, &dir).fetch(2019, 2).unwrap_err();
+
+    assert_eq!(error.to_string(), "Day 2 of 2019 isn't available yet");
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn requests_are_spaced_out_between_runs() {
+    let server = MockServer::start();
+    let dir = inputs_dir("spaced");
+    let interval = Duration::from_millis(300);
+    for day in 1..=2 {
+        // A new fetcher each time, as if fetch had been run twice
+        let mut fetcher = server.fetcher(SESSION, &dir);
+        fetcher.set_min_interval(interval);
+        let _ = fetcher.fetch(2019, day);
+    }
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[1].received - requests[0].received >= interval);
+    fs::remove_dir_all(dir).unwrap();
+}
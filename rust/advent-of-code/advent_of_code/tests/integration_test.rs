@@ -1,9 +1,39 @@
 use advent_of_code::error::Error;
+use advent_of_code::input::{read_input, DEFAULT_INPUTS_DIR};
+use advent_of_code::solution::Solution;
+use advent_of_code::year_2019::{day_five, day_two};
 use advent_of_code::*;
+use std::path::Path;
+
+fn input(day: usize) -> Result<String, Error> {
+    read_input(2019, day, None, Path::new(DEFAULT_INPUTS_DIR))
+}
 
 #[test]
 fn day_two_expected_result() -> Result<(), Error> {
-    let result = day_two::run()?;
-    assert_eq!(result, vec![3760627, 19690720]);
+    let program = day_two::DayTwo::parse(&input(2)?)?;
+    assert_eq!(day_two::DayTwo::part_one(&program)?, 3760627);
+    assert_eq!(day_two::DayTwo::part_two(&program)?, 19690720);
+    Ok(())
+}
+
+#[test]
+fn day_five_expected_result() -> Result<(), Error> {
+    let program = day_five::DayFive::parse(&input(5)?)?;
+    assert_eq!(day_five::DayFive::part_one(&program)?, 10987514);
+    assert_eq!(day_five::DayFive::part_two(&program)?, 14195011);
+    Ok(())
+}
+
+#[test]
+fn puzzle_programs_round_trip_through_the_assembler() -> Result<(), Error> {
+    for path in &[
+        "./inputs/2019/day_two/input.txt",
+        "./inputs/2019/day_five/input.txt",
+    ] {
+        let program = computer::parse_program(&std::fs::read_to_string(path)?)?;
+        let source = computer::disassembler::source(&program);
+        assert_eq!(computer::assembler::assemble(&source)?, program);
+    }
     Ok(())
 }